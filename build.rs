@@ -1,6 +1,12 @@
 #[cfg(unix)]
 fn main() {
-  println!("cargo:rustc-link-lib=dylib=ncurses");
+  // The wide character functions are in a separate library on most systems,
+  // but macOS puts everything in the main library.
+  if cfg!(target_os = "macos") {
+    println!("cargo:rustc-link-lib=dylib=ncurses");
+  } else {
+    println!("cargo:rustc-link-lib=dylib=ncursesw");
+  }
 }
 
 #[cfg(windows)]
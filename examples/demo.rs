@@ -72,14 +72,15 @@ fn main() {
       Some(BANG) => panic!("test panic"),
       Some(P) => {
         let sh = win.shell_mode().unwrap();
-        for cid in 0..8 {
-          let [r, g, b] = sh.get_color_id_rgb(ColorID(cid)).unwrap();
-          eprintln!("CID({}): [{},{},{}]", cid, r, g, b);
+        for cid in sh.color_ids().take(8) {
+          let [r, g, b] = sh.get_color_id_rgb(cid).unwrap();
+          eprintln!("CID({}): [{},{},{}]", cid.0, r, g, b);
         }
         eprintln!("{:?}", sh.get_cursor_position());
         eprintln!("{:?}", sh.get_terminal_size());
         eprintln!("{:?}", sh.get_max_color_id_inclusive());
         eprintln!("{:?}", sh.get_max_color_pair_inclusive());
+        eprintln!("{:?}", sh.capabilities());
         let mut str_buf = String::with_capacity(1024);
         std::io::stdin().read_line(&mut str_buf).unwrap();
         println!("got line: {}", str_buf);
@@ -9,6 +9,7 @@
 use std::os::raw::*;
 
 pub type chtype = c_uint;
+pub type attr_t = chtype;
 
 pub const COLOR_BLACK: u32 = 0;
 pub const COLOR_RED: u32 = 1;
@@ -19,6 +20,7 @@ pub const COLOR_MAGENTA: u32 = 5;
 pub const COLOR_CYAN: u32 = 6;
 pub const COLOR_WHITE: u32 = 7;
 pub const ERR: i32 = -1;
+pub const KEY_CODE_YES: i32 = 256;
 pub const KEY_DOWN: u32 = 258;
 pub const KEY_UP: u32 = 259;
 pub const KEY_LEFT: u32 = 260;
@@ -31,21 +33,57 @@ pub const KEY_IC: u32 = 331;
 pub const KEY_NPAGE: u32 = 338;
 pub const KEY_PPAGE: u32 = 339;
 pub const KEY_ENTER: u32 = 343;
-pub const KEY_RESIZE: u32 = 410;
 
 #[repr(transparent)]
 pub struct WINDOW(c_void);
 
+#[repr(transparent)]
+pub struct FILE(c_void);
+
+#[repr(transparent)]
+pub struct SCREEN(c_void);
+
+// C standard library, for passing files to curses.
+extern "C" {
+  pub fn fclose(stream: *mut FILE) -> c_int;
+
+  pub fn fflush(stream: *mut FILE) -> c_int;
+
+  pub fn fputc(c: c_int, stream: *mut FILE) -> c_int;
+
+  pub fn fread(
+    ptr: *mut c_void, size: usize, nmemb: usize, stream: *mut FILE,
+  ) -> usize;
+
+  pub fn fwrite(
+    ptr: *const c_void, size: usize, nmemb: usize, stream: *mut FILE,
+  ) -> usize;
+
+  pub fn putchar(c: c_int) -> c_int;
+
+  pub fn rewind(stream: *mut FILE);
+
+  pub fn tmpfile() -> *mut FILE;
+}
+
 // Note(Lokathor): READ ONLY!
 extern "C" {
   pub static mut stdscr: *mut WINDOW;
 
+  pub static mut curscr: *mut WINDOW;
+
   pub static mut COLORS: c_int;
 
   pub static mut COLOR_PAIRS: c_int;
+
+  pub static mut TABSIZE: c_int;
 }
 
 extern "C" {
+  pub fn assume_default_colors(arg1: c_int, arg2: c_int) -> c_int;
+
+  pub fn beep() -> c_int;
+
   pub fn can_change_color() -> bool;
 
   pub fn cbreak() -> c_int;
@@ -58,16 +96,38 @@ extern "C" {
 
   pub fn def_prog_mode() -> c_int;
 
+  pub fn def_shell_mode() -> c_int;
+
+  pub fn delay_output(ms: c_int) -> c_int;
+
+  pub fn delscreen(arg1: *mut SCREEN);
+
+  pub fn delwin(arg1: *mut WINDOW) -> c_int;
+
+  pub fn doupdate() -> c_int;
+
   pub fn echo() -> c_int;
 
   pub fn endwin() -> c_int;
 
+  pub fn erasechar() -> c_char;
+
+  pub fn flash() -> c_int;
+
   pub fn flushinp() -> c_int;
 
   pub fn getbkgd(arg1: *mut WINDOW) -> chtype;
 
+  pub fn getwin(arg1: *mut FILE) -> *mut WINDOW;
+
   pub fn has_colors() -> bool;
 
+  pub fn idcok(arg1: *mut WINDOW, arg2: bool);
+
+  pub fn idlok(arg1: *mut WINDOW, arg2: bool) -> c_int;
+
+  pub fn immedok(arg1: *mut WINDOW, arg2: bool);
+
   pub fn initscr() -> *mut WINDOW;
 
   pub fn init_color(
@@ -76,23 +136,88 @@ extern "C" {
 
   pub fn init_pair(arg1: c_short, arg2: c_short, arg3: c_short) -> c_int;
 
+  pub fn intrflush(arg1: *mut WINDOW, arg2: bool) -> c_int;
+
   pub fn isendwin() -> bool;
 
+  pub fn is_linetouched(arg1: *mut WINDOW, arg2: c_int) -> bool;
+
+  pub fn is_wintouched(arg1: *mut WINDOW) -> bool;
+
+  pub fn keyname(arg1: c_int) -> *const c_char;
+
   pub fn keypad(arg1: *mut WINDOW, arg2: bool) -> c_int;
 
+  pub fn killchar() -> c_char;
+
+  pub fn meta(arg1: *mut WINDOW, arg2: bool) -> c_int;
+
+  pub fn mvwaddch(
+    arg1: *mut WINDOW, arg2: c_int, arg3: c_int, arg4: chtype,
+  ) -> c_int;
+
+  pub fn mvwchgat(
+    arg1: *mut WINDOW, arg2: c_int, arg3: c_int, arg4: c_int, arg5: attr_t,
+    arg6: c_short, arg7: *const c_void,
+  ) -> c_int;
+
+  pub fn mvwinch(arg1: *mut WINDOW, arg2: c_int, arg3: c_int) -> chtype;
+
+  pub fn mvwinchnstr(
+    arg1: *mut WINDOW, arg2: c_int, arg3: c_int, arg4: *mut chtype, arg5: c_int,
+  ) -> c_int;
+
+  pub fn mvwinnstr(
+    arg1: *mut WINDOW, arg2: c_int, arg3: c_int, arg4: *mut c_char, arg5: c_int,
+  ) -> c_int;
+
+  pub fn nl() -> c_int;
+
+  pub fn nocbreak() -> c_int;
+
   pub fn noecho() -> c_int;
 
+  pub fn nonl() -> c_int;
+
+  pub fn overwrite(arg1: *const WINDOW, arg2: *mut WINDOW) -> c_int;
+
   pub fn pair_content(
     arg1: c_short, arg2: *mut c_short, arg3: *mut c_short,
   ) -> c_int;
 
+  pub fn putwin(arg1: *mut WINDOW, arg2: *mut FILE) -> c_int;
+
+  pub fn redrawwin(arg1: *mut WINDOW) -> c_int;
+
+  pub fn reset_prog_mode() -> c_int;
+
+  pub fn reset_shell_mode() -> c_int;
+
   pub fn resize_term(lines: c_int, columns: c_int) -> c_int;
 
+  pub fn scr_restore(filename: *const c_char) -> c_int;
+
   pub fn scrollok(arg1: *mut WINDOW, arg2: bool) -> c_int;
 
+  pub fn set_term(arg1: *mut SCREEN) -> *mut SCREEN;
+
+  pub fn slk_init(fmt: c_int) -> c_int;
+
+  pub fn slk_noutrefresh() -> c_int;
+
+  pub fn slk_refresh() -> c_int;
+
+  pub fn slk_set(labnum: c_int, label: *const c_char, justify: c_int) -> c_int;
+
   pub fn start_color() -> c_int;
 
-  pub fn ungetch(arg1: c_int) -> c_int;
+  pub fn termattrs() -> chtype;
+
+  pub fn termname() -> *mut c_char;
+
+  pub fn typeahead(fd: c_int) -> c_int;
+
+  pub fn use_default_colors() -> c_int;
 
   pub fn waddch(arg1: *mut WINDOW, arg2: chtype) -> c_int;
 
@@ -103,12 +228,32 @@ extern "C" {
   pub fn waddnstr(arg1: *mut WINDOW, arg2: *const c_char, arg3: c_int)
     -> c_int;
 
+  pub fn wattr_get(
+    arg1: *mut WINDOW, arg2: *mut attr_t, arg3: *mut c_short, arg4: *mut c_void,
+  ) -> c_int;
+
+  pub fn wattr_off(arg1: *mut WINDOW, arg2: attr_t, arg3: *mut c_void)
+    -> c_int;
+
+  pub fn wattr_on(arg1: *mut WINDOW, arg2: attr_t, arg3: *mut c_void) -> c_int;
+
+  pub fn wattr_set(
+    arg1: *mut WINDOW, arg2: attr_t, arg3: c_short, arg4: *mut c_void,
+  ) -> c_int;
+
   pub fn wattron(arg1: *mut WINDOW, arg2: c_int) -> c_int;
 
   pub fn wattroff(arg1: *mut WINDOW, arg2: c_int) -> c_int;
 
   pub fn wbkgd(arg1: *mut WINDOW, arg2: chtype) -> c_int;
 
+  pub fn wbkgdset(arg1: *mut WINDOW, arg2: chtype);
+
+  pub fn wborder(
+    arg1: *mut WINDOW, arg2: chtype, arg3: chtype, arg4: chtype, arg5: chtype,
+    arg6: chtype, arg7: chtype, arg8: chtype, arg9: chtype,
+  ) -> c_int;
+
   pub fn wclear(arg1: *mut WINDOW) -> c_int;
 
   pub fn wcolor_set(
@@ -131,6 +276,10 @@ extern "C" {
 
   pub fn wtimeout(arg1: *mut WINDOW, arg2: c_int);
 
+  pub fn getbegx(arg1: *const WINDOW) -> c_int;
+
+  pub fn getbegy(arg1: *const WINDOW) -> c_int;
+
   pub fn getcurx(arg1: *const WINDOW) -> c_int;
 
   pub fn getcury(arg1: *const WINDOW) -> c_int;
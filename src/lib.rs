@@ -48,14 +48,28 @@
 //! A side effect of this is that if you also wanted to have your own panic hook
 //! going on, then there can end up being conflicts. Sorry about that, not much
 //! can be done there.
+//!
+//! ## Features
+//!
+//! * `serde`: Derives `Serialize` and `Deserialize` for the plain data types
+//!   ([`Position`], [`TerminalSize`], [`Rect`], [`CursesGlyph`], [`ColorID`],
+//!   [`ColorPair`], and [`Attributes`]).
 
 use core::{
   convert::{TryFrom, TryInto},
+  fmt,
   mem::replace,
   num::NonZeroU8,
   ops::*,
   panic::PanicInfo,
-  sync::atomic::{AtomicBool, Ordering},
+  sync::atomic::{AtomicBool, AtomicI32, Ordering},
+  time::Duration,
+};
+use std::{
+  ffi::CString,
+  fs::File,
+  io::{Read, Write},
+  path::Path,
 };
 
 mod curses_common;
@@ -102,19 +116,67 @@ macro_rules! unsafe_void {
 type PanicHook = Box<dyn Fn(&PanicInfo) + Sync + Send + 'static>;
 
 /// Handle to the terminal's curses interface.
+///
+/// This is not `Clone`: curses has a single global screen, and only one
+/// `Curses` can exist at a time. Methods that change the terminal take
+/// `&mut self` so that the borrow checker keeps that access exclusive.
 #[repr(C)]
 pub struct Curses {
   ptr: *mut WINDOW,
   old_hook: PanicHook,
+  normalize_backspace: bool,
+  logical_size: Option<TerminalSize>,
+  color_available: bool,
+  alert_preference: AlertPreference,
+  /// The colors of each pair handed out by `use_colors`, counting down from
+  /// the highest color pair.
+  auto_color_pairs: Vec<(ColorID, ColorID)>,
+  /// Set if the screen has to be deleted when this drops (eg: it was made by
+  /// `new_term`).
+  new_term_screen: Option<NewTermScreen>,
+}
+/// A screen made with `newterm`, along with the streams it uses.
+///
+/// Unlike the normal screen this one isn't resumed later, so it's deleted
+/// when the `Curses` drops.
+#[cfg_attr(windows, allow(dead_code))]
+struct NewTermScreen {
+  screen: *mut SCREEN,
+  out: *mut FILE,
+  input: *mut FILE,
+  /// The screen that was set aside to make this one, if any. Deleting any
+  /// screen frees the windows of every screen, so it can't be resumed later
+  /// and is deleted along with this one.
+  previous: *mut SCREEN,
 }
 static CURSES_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// `initscr` only works once per process. If the screen it made gets deleted
+/// (by `new_term`), later starts have to use `newterm` instead.
+#[cfg(unix)]
+static INITSCR_USED: AtomicBool = AtomicBool::new(false);
+/// Curses can't report the input timeout (pdcurses has no `wgetdelay`), so we
+/// remember the last value we set. It lives outside of `Curses` because the
+/// setting stays in effect if curses mode is ended and later resumed.
+static CURSES_TIMEOUT: AtomicI32 = AtomicI32::new(-1);
 impl Drop for Curses {
   fn drop(&mut self) {
     // Save the settings before we shut down curses, in case it's resumed later.
-    // in case of error, we just accept it.
-    unsafe_always_ok!(def_prog_mode());
+    // in case of error (eg: the input isn't a tty), we just accept it.
+    let _ = unsafe_call_result!("", def_prog_mode());
     // In case of panic, curses mode will already be off.
     let _ = unsafe { endwin() };
+    if let Some(nt) = self.new_term_screen.take() {
+      unsafe {
+        delscreen(nt.screen);
+        if !nt.previous.is_null() {
+          delscreen(nt.previous);
+        }
+        fclose(nt.out);
+        fclose(nt.input);
+      }
+      // The next screen will be a fresh one, which starts out blocking.
+      CURSES_TIMEOUT.store(-1, Ordering::SeqCst);
+    }
     CURSES_ACTIVE.store(false, Ordering::SeqCst);
     // If not in a panic, restore the old panic hook. Changing the hook isn't
     // allowed during a panic.
@@ -139,53 +201,534 @@ impl Curses {
   /// * This installs a custom panic hook that ends curses mode before printing
   ///   the panic message. Otherwise your panic messages get eaten. The normal
   ///   panic hook is restored when `Curses` drops.
+  /// * See [`try_init`](Curses::try_init) for a version that returns errors
+  ///   instead.
   pub fn init() -> Self {
     if CURSES_ACTIVE
       .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
       .is_ok()
     {
-      // If we're about to go into curses mode, grab the current hook to
-      // save for later and install a hook that will turn off curses, print the
-      // panic info, and then restore curses.
-      let old_hook = std::panic::take_hook();
-      std::panic::set_hook(Box::new(|panic_info| {
-        unsafe_always_ok!(def_prog_mode());
-        let _ = unsafe_call_result!("", endwin());
-        eprintln!("{}", panic_info);
-      }));
       if unsafe { isendwin() } {
-        let mut w = Self { ptr: unsafe { stdscr }, old_hook };
-        w.refresh().unwrap();
-        w
+        Self::resume()
       } else {
-        let win = Self { ptr: unsafe { initscr() }, old_hook };
-        assert!(!win.ptr.is_null());
-        // technically this could fail to allocate the color table, but if so
-        // we'll just get other errors if people do use color later on. If we
-        // failed to allocate the color table but color isn't used, then there's
-        // no reason to raise a fuss.
-        let _ = unsafe_call_result!("", start_color());
-        // this only fails if curses isn't init or the ptr is null, so it should
-        // never fail here since we checked for null already. However, if it
-        // somehow does fail anyway, then the worst that happens is that the
-        // user can't use the keypad keys.
-        let _ = unsafe_call_result!("", keypad(win.ptr, true));
-        // We always want to operate in cbreak mode, so set it here and don't
-        // expose this option to the user. In this case, if `cbreak` isn't set
-        // then things will be weird as hell, so we panic on failure.
-        unsafe_call_result!("", cbreak()).expect("Couldn't set `cbreak` mode.");
-        win
+        Self::start_with_initscr()
       }
     } else {
       panic!("Curses is already active.")
     }
   }
 
+  /// Initializes curses, returning an error instead of panicking or aborting
+  /// where possible.
+  ///
+  /// Otherwise this is the same as [`init`](Curses::init).
+  ///
+  /// * On Unix this checks that `TERM` is set, and then starts curses with
+  ///   `newterm` on `stdout` and `stdin`, which reports failure instead of
+  ///   aborting the process the way `initscr` does.
+  /// * On Windows only the "already active" check can be reported, since
+  ///   pdcurses always exits the process if it can't start.
+  pub fn try_init() -> Result<Self, CursesInitError> {
+    if CURSES_ACTIVE
+      .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+      .is_err()
+    {
+      return Err(CursesInitError::AlreadyActive);
+    }
+    if unsafe { isendwin() } {
+      return Ok(Self::resume());
+    }
+    #[cfg(unix)]
+    {
+      if !matches!(std::env::var_os("TERM"), Some(t) if !t.is_empty()) {
+        CURSES_ACTIVE.store(false, Ordering::SeqCst);
+        return Err(CursesInitError::NoTerm);
+      }
+      Self::start_with_newterm()
+    }
+    #[cfg(windows)]
+    {
+      Ok(Self::start_with_initscr())
+    }
+  }
+
+  /// Starts a fresh screen on `stdout` and `stdin` with `newterm`.
+  ///
+  /// The caller must have already set `CURSES_ACTIVE`, which is cleared again
+  /// on error.
+  #[cfg(unix)]
+  fn start_with_newterm() -> Result<Self, CursesInitError> {
+    use std::os::unix::io::FromRawFd;
+    // Curses gets copies of the fds, so that its files can be closed if
+    // starting fails without closing the process's own stdout and stdin.
+    let open = |fd: i32, mode: &[u8]| unsafe {
+      let copy = dup(fd);
+      if copy < 0 {
+        return core::ptr::null_mut();
+      }
+      let fp = fdopen(copy, mode.as_ptr().cast());
+      if fp.is_null() {
+        drop(File::from_raw_fd(copy));
+      }
+      fp
+    };
+    let out_fp = open(1, b"w\0");
+    let in_fp = open(0, b"r\0");
+    let screen = if out_fp.is_null() || in_fp.is_null() {
+      core::ptr::null_mut()
+    } else {
+      // A null terminal type means to use `TERM`.
+      unsafe { newterm(core::ptr::null(), out_fp, in_fp) }
+    };
+    if screen.is_null() {
+      unsafe {
+        if !out_fp.is_null() {
+          fclose(out_fp);
+        }
+        if !in_fp.is_null() {
+          fclose(in_fp);
+        }
+      }
+      CURSES_ACTIVE.store(false, Ordering::SeqCst);
+      return Err(CursesInitError::UnknownTerminal);
+    }
+    let mut win = Self {
+      ptr: unsafe { stdscr },
+      old_hook: Self::install_panic_hook(),
+      normalize_backspace: false,
+      logical_size: None,
+      color_available: false,
+      alert_preference: AlertPreference::Auto,
+      auto_color_pairs: Vec::new(),
+      new_term_screen: None,
+    };
+    if win.setup_new_screen().is_err() {
+      // Dropping `win` ends curses mode again. The screen is deleted too,
+      // otherwise a later `try_init` would resume it without `cbreak` set.
+      win.new_term_screen = Some(NewTermScreen {
+        screen,
+        out: out_fp,
+        input: in_fp,
+        previous: core::ptr::null_mut(),
+      });
+      return Err(CursesInitError::NoCbreak);
+    }
+    Ok(win)
+  }
+
+  /// Resumes curses mode after an earlier `Curses` was dropped.
+  ///
+  /// The caller must have already set `CURSES_ACTIVE`.
+  fn resume() -> Self {
+    let old_hook = Self::install_panic_hook();
+    // The shell's tty settings might have changed since curses was last
+    // active, so save them again for the next `endwin`.
+    let _ = unsafe_call_result!("", def_shell_mode());
+    let mut w = Self {
+      ptr: unsafe { stdscr },
+      old_hook,
+      normalize_backspace: false,
+      logical_size: None,
+      // color was already started when curses was first initialized.
+      color_available: unsafe { has_colors() },
+      alert_preference: AlertPreference::Auto,
+      auto_color_pairs: Vec::new(),
+      new_term_screen: None,
+    };
+    w.refresh().unwrap();
+    w
+  }
+
+  /// Starts a fresh screen with `initscr`, or with `newterm` if `initscr`
+  /// has already been used.
+  ///
+  /// The caller must have already set `CURSES_ACTIVE`.
+  fn start_with_initscr() -> Self {
+    #[cfg(unix)]
+    {
+      if INITSCR_USED.swap(true, Ordering::SeqCst) {
+        return Self::start_with_newterm().expect("Couldn't start curses.");
+      }
+    }
+    let mut win = Self {
+      ptr: unsafe { initscr() },
+      old_hook: Self::install_panic_hook(),
+      normalize_backspace: false,
+      logical_size: None,
+      color_available: false,
+      alert_preference: AlertPreference::Auto,
+      auto_color_pairs: Vec::new(),
+      new_term_screen: None,
+    };
+    assert!(!win.ptr.is_null());
+    // We always want to start in cbreak mode. In this case, if `cbreak`
+    // isn't set then things will be weird as hell, so we panic on failure.
+    win.setup_new_screen().expect("Couldn't set `cbreak` mode.");
+    win
+  }
+
+  /// Initializes curses with soft label keys.
+  ///
+  /// Soft labels are a row of short labels along the bottom of the screen,
+  /// usually used to show what the function keys do. They take up the bottom
+  /// line(s) of the terminal, so the rest of the screen is that much shorter.
+  ///
+  /// This works like [`init`](Curses::init), with the same panics. Soft labels
+  /// must be set up before curses first starts, so if curses mode is being
+  /// resumed after an earlier `Curses` was dropped then the layout has no
+  /// effect.
+  pub fn init_with_soft_labels(layout: SoftLabelLayout) -> Self {
+    // When resuming, `slk_init` would instead apply to whatever screen is
+    // started next, so it's skipped.
+    if !CURSES_ACTIVE.load(Ordering::SeqCst) && !unsafe { isendwin() } {
+      // This can only fail on a bad layout value, which the enum prevents.
+      let _ = unsafe_call_result!("", slk_init(layout as _));
+    }
+    Self::init()
+  }
+
+  /// Initializes curses on a terminal given by a pair of files, instead of
+  /// the process's own terminal.
+  ///
+  /// This uses `newterm` rather than `initscr`. The output for a terminal of
+  /// type `term` (eg: `"xterm-256color"`) is written to `out`, and input is
+  /// read from `input`. Since the real tty is never touched, this allows
+  /// headless testing of rendering code (for example, using a pseudo-terminal
+  /// or a plain file for `out`).
+  ///
+  /// * The files are handed over to curses, and are closed when the `Curses`
+  ///   drops.
+  /// * The screen isn't kept around after the `Curses` drops, so a later
+  ///   [`init`](Curses::init) starts over on the real terminal. Any earlier
+  ///   curses session on the real terminal is discarded at that point too,
+  ///   rather than being resumed. If this errors, the earlier session is left
+  ///   as it was.
+  /// * If `input` isn't a tty then `cbreak` mode can't be set, and curses
+  ///   treats input as line buffered.
+  /// * Errors if curses is already active, if `term` contains a null byte, if
+  ///   curses doesn't know the terminal type, or if the files can't be used.
+  /// * On Windows this always errors, because pdcurses always uses the
+  ///   console.
+  pub fn new_term(
+    term: &str, out: File, input: File,
+  ) -> Result<Self, &'static str> {
+    #[cfg(unix)]
+    {
+      use std::os::unix::io::{FromRawFd, IntoRawFd};
+      let term = CString::new(term).map_err(|_| "new_term")?;
+      if CURSES_ACTIVE
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+      {
+        return Err("new_term");
+      }
+      let open = |file: File, mode: &[u8]| {
+        let fd = file.into_raw_fd();
+        let fp = unsafe { fdopen(fd, mode.as_ptr().cast()) };
+        if fp.is_null() {
+          // Hand the fd back to a `File` so that it gets closed.
+          drop(unsafe { File::from_raw_fd(fd) });
+        }
+        fp
+      };
+      let out_fp = open(out, b"w\0");
+      let in_fp = open(input, b"r\0");
+      // If curses was started on the real terminal before, set that screen
+      // aside. It's only deleted once the new screen is done with, so if
+      // `newterm` fails it can be made current again.
+      let previous = unsafe { set_term(core::ptr::null_mut()) };
+      let screen = if out_fp.is_null() || in_fp.is_null() {
+        core::ptr::null_mut()
+      } else {
+        unsafe { newterm(term.as_ptr(), out_fp, in_fp) }
+      };
+      if screen.is_null() {
+        unsafe {
+          if !out_fp.is_null() {
+            fclose(out_fp);
+          }
+          if !in_fp.is_null() {
+            fclose(in_fp);
+          }
+          if !previous.is_null() {
+            set_term(previous);
+          }
+        }
+        CURSES_ACTIVE.store(false, Ordering::SeqCst);
+        return Err("new_term");
+      }
+      // The new screen starts out blocking.
+      CURSES_TIMEOUT.store(-1, Ordering::SeqCst);
+      let mut win = Self {
+        ptr: unsafe { stdscr },
+        old_hook: Self::install_panic_hook(),
+        normalize_backspace: false,
+        logical_size: None,
+        color_available: false,
+        alert_preference: AlertPreference::Auto,
+        auto_color_pairs: Vec::new(),
+        new_term_screen: Some(NewTermScreen {
+          screen,
+          out: out_fp,
+          input: in_fp,
+          previous,
+        }),
+      };
+      // The input might not be a tty (eg: when testing), so here we allow
+      // cbreak mode to fail.
+      let _ = win.setup_new_screen();
+      Ok(win)
+    }
+    #[cfg(windows)]
+    {
+      let _ = (term, out, input);
+      Err("new_term")
+    }
+  }
+
+  /// Installs a panic hook that ends curses mode before printing the panic
+  /// info, and returns the old hook so that it can be restored later.
+  fn install_panic_hook() -> PanicHook {
+    let old_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|panic_info| {
+      let _ = unsafe_call_result!("", def_prog_mode());
+      let _ = unsafe_call_result!("", endwin());
+      eprintln!("{}", panic_info);
+    }));
+    old_hook
+  }
+
+  /// The settings we always apply to a freshly created screen.
+  ///
+  /// Errors if `cbreak` mode couldn't be set.
+  fn setup_new_screen(&mut self) -> Result<(), &'static str> {
+    // technically this could fail to allocate the color table, but if so we
+    // just remember that color isn't available, and the color methods will
+    // report that if people do use color later on. If color isn't used, then
+    // there's no reason to raise a fuss.
+    self.color_available =
+      unsafe { has_colors() } && unsafe_call_result!("", start_color()).is_ok();
+    // this only fails if curses isn't init or the ptr is null, so it should
+    // never fail here since we checked for null already. However, if it
+    // somehow does fail anyway, then the worst that happens is that the
+    // user can't use the keypad keys.
+    let _ = unsafe_call_result!("", keypad(self.ptr, true));
+    unsafe_call_result!("", cbreak())
+  }
+
+  /// Initializes curses, then loads a screen previously saved with curses'
+  /// `scr_dump` and shows it.
+  ///
+  /// This works like [`init`](Curses::init), with the same panics. The loaded
+  /// screen becomes what curses believes is on the display and is drawn right
+  /// away, so your own drawing can continue from where the saved session left
+  /// off. Only cells that you change will be drawn over it.
+  ///
+  /// If the file can't be loaded the error is returned and the new `Curses`
+  /// is dropped, ending curses mode again.
+  pub fn init_screen_from(path: &Path) -> Result<Self, &'static str> {
+    let c_path = path
+      .to_str()
+      .and_then(|s| CString::new(s).ok())
+      .ok_or("init_screen_from")?;
+    let win = Self::init();
+    win.draw_screen_dump(&c_path)?;
+    Ok(win)
+  }
+
+  /// Loads a screen dump as the next screen to show, and draws it.
+  fn draw_screen_dump(&self, path: &CString) -> Result<(), &'static str> {
+    // `scr_set` would also make the dump what curses thinks is already on the
+    // display, and then `doupdate` wouldn't send anything.
+    unsafe_call_result!("init_screen_from", scr_restore(path.as_ptr()))?;
+    unsafe_call_result!("init_screen_from", doupdate())?;
+    // Our window gets the same contents, otherwise the next refresh would
+    // draw it (blank) over the whole dump.
+    unsafe_call_result!("init_screen_from", overwrite(curscr, self.ptr))
+  }
+
+  /// Writes out the full contents of the window (text, attributes, and
+  /// colors) in curses' own format.
+  ///
+  /// Load it back later with [`load_window`](Curses::load_window). The data
+  /// passes through a temporary file, because curses can only write to a C
+  /// `FILE`.
+  pub fn save_window<W: Write>(&self, mut w: W) -> Result<(), &'static str> {
+    let fp = unsafe { tmpfile() };
+    if fp.is_null() {
+      return Err("save_window");
+    }
+    let mut copy_out = || {
+      unsafe_call_result!("save_window", putwin(self.ptr, fp))?;
+      unsafe { rewind(fp) };
+      let mut buf = [0_u8; 4096];
+      loop {
+        let n = unsafe { fread(buf.as_mut_ptr().cast(), 1, buf.len(), fp) };
+        if n == 0 {
+          return Ok(());
+        }
+        w.write_all(&buf[..n]).map_err(|_| "save_window")?;
+      }
+    };
+    let result = copy_out();
+    unsafe { fclose(fp) };
+    result
+  }
+
+  /// Reads window contents written by [`save_window`](Curses::save_window)
+  /// and copies them onto the screen.
+  ///
+  /// * If the saved window was a different size, only the part that overlaps
+  ///   the current screen is copied.
+  /// * The cursor doesn't move, and the new contents show up on the next
+  ///   [`refresh`](Curses::refresh).
+  pub fn load_window<R: Read>(&mut self, mut r: R) -> Result<(), &'static str> {
+    let mut bytes = Vec::new();
+    r.read_to_end(&mut bytes).map_err(|_| "load_window")?;
+    let fp = unsafe { tmpfile() };
+    if fp.is_null() {
+      return Err("load_window");
+    }
+    let written = unsafe { fwrite(bytes.as_ptr().cast(), 1, bytes.len(), fp) };
+    let result = if written != bytes.len() {
+      Err("load_window")
+    } else {
+      unsafe { rewind(fp) };
+      let win = unsafe { getwin(fp) };
+      if win.is_null() {
+        Err("load_window")
+      } else {
+        let out = unsafe_call_result!("load_window", overwrite(win, self.ptr));
+        unsafe_always_ok!(delwin(win));
+        out
+      }
+    };
+    unsafe { fclose(fp) };
+    result
+  }
+
   /// Pushes all updates out to the physical screen, refreshing the display.
   pub fn refresh(&mut self) -> Result<(), &'static str> {
     unsafe_call_result!("refresh", wrefresh(self.ptr))
   }
 
+  /// Sets the text of a soft label.
+  ///
+  /// Labels are numbered starting from 1. This requires that curses was
+  /// started with [`init_with_soft_labels`](Curses::init_with_soft_labels).
+  ///
+  /// * The text is cut off to fit the label (8 cells for most layouts).
+  /// * Errors if the label number isn't part of the layout, or if the text
+  ///   contains a null byte.
+  /// * The change isn't shown until you call
+  ///   [`refresh_soft_labels`](Curses::refresh_soft_labels).
+  pub fn set_soft_label(
+    &mut self, n: u8, text: &str, align: Align,
+  ) -> Result<(), &'static str> {
+    let text = CString::new(text).map_err(|_| "set_soft_label")?;
+    let justify = match align {
+      Align::Left => 0,
+      Align::Center => 1,
+      Align::Right => 2,
+    };
+    unsafe_call_result!(
+      "set_soft_label",
+      slk_set(n.into(), text.as_ptr(), justify)
+    )
+  }
+
+  /// Pushes the soft labels out to the physical screen.
+  pub fn refresh_soft_labels(&mut self) -> Result<(), &'static str> {
+    unsafe_call_result!("refresh_soft_labels", slk_refresh())
+  }
+
+  /// Marks the soft labels to be drawn, without updating the physical screen.
+  ///
+  /// They'll be drawn along with the next [`refresh`](Curses::refresh). This
+  /// avoids an extra screen update compared to calling both `refresh` and
+  /// [`refresh_soft_labels`](Curses::refresh_soft_labels).
+  pub fn stage_soft_labels(&mut self) -> Result<(), &'static str> {
+    unsafe_call_result!("stage_soft_labels", slk_noutrefresh())
+  }
+
+  /// If anything has been drawn since the last [`refresh`](Curses::refresh).
+  ///
+  /// When this is `false` you can skip refreshing, since nothing would change.
+  pub fn is_dirty(&self) -> bool {
+    unsafe { is_wintouched(self.ptr) }
+  }
+
+  /// If anything in the given row has been drawn since the last
+  /// [`refresh`](Curses::refresh).
+  ///
+  /// Rows off of the screen are never dirty.
+  pub fn line_dirty(&self, y: u32) -> bool {
+    // curses returns `ERR` (not a valid `bool`) for an out of bounds row, so
+    // we must check the row ourselves first.
+    if y >= self.get_physical_terminal_size().y_count {
+      return false;
+    }
+    unsafe { is_linetouched(self.ptr, y as _) }
+  }
+
+  /// Gets a guard that refreshes the screen once when it's dropped.
+  ///
+  /// Everything drawn through the guard shows up at the end of the scope,
+  /// even if you forget to call [`refresh`](Curses::refresh). Unlike
+  /// [`set_immediate`](Curses::set_immediate), there's only the one refresh,
+  /// so it's cheap.
+  pub fn auto_refresh<'a>(&'a mut self) -> AutoRefresh<'a> {
+    AutoRefresh { win: self }
+  }
+
+  /// Gets a writer that collects glyphs and draws them in batches.
+  ///
+  /// Drawing a glyph at a time is one call into curses per glyph, while the
+  /// writer draws each run of glyphs with a single call. The screen is
+  /// refreshed when the writer is [flushed](GlyphWriter::flush) or dropped.
+  pub fn writer<'a>(&'a mut self) -> GlyphWriter<'a> {
+    GlyphWriter { win: self, pending: Vec::new() }
+  }
+
+  /// Sets if every change to the window should immediately refresh the
+  /// display.
+  ///
+  /// This is handy while debugging, or for very simple scripts, because you
+  /// don't have to remember to call [`refresh`](Curses::refresh). However, it
+  /// makes all drawing much slower, so don't use it in a render loop.
+  ///
+  /// * Off by default.
+  pub fn set_immediate(&mut self, on: bool) {
+    unsafe_void!(immedok(self.ptr, on))
+  }
+
+  /// Inserts a pause of the given number of milliseconds into the output.
+  ///
+  /// This pads the output stream sent to the terminal (when the terminal
+  /// supports padding), rather than putting your program to sleep the way
+  /// something like `napms` or [`std::thread::sleep`] would. It's useful for
+  /// "typewriter" style text effects.
+  pub fn delay_output(&mut self, ms: i32) -> Result<(), &'static str> {
+    unsafe_call_result!("delay_output", delay_output(ms))
+  }
+
+  /// Sets the file descriptor that curses checks for pending input while
+  /// refreshing the display.
+  ///
+  /// When curses sees pending input ("typeahead") during a refresh it may
+  /// stop the refresh early so that the input can be processed first. During a
+  /// big redraw this can cause visible tearing.
+  ///
+  /// * `Some(fd)`: check the given file descriptor for typeahead.
+  /// * `None`: disable typeahead checking, refreshes always run to completion.
+  ///
+  /// By default curses checks `stdin`.
+  pub fn set_typeahead_check(
+    &mut self, fd: Option<i32>,
+  ) -> Result<(), &'static str> {
+    unsafe_call_result!("set_typeahead_check", typeahead(fd.unwrap_or(-1)))
+  }
+
   /// Sets if user inputs should automatically echo to the screen or not.
   ///
   /// * Initially this is enabled.
@@ -197,6 +740,85 @@ impl Curses {
     }
   }
 
+  /// Sets if the terminal is in "cbreak" mode.
+  ///
+  /// * On: each key press is available as soon as it's typed. This is on by
+  ///   default.
+  /// * Off: the terminal does line buffering, so input isn't available to
+  ///   [`poll_events`](Curses::poll_events) until the user presses Enter. The
+  ///   user can edit the line with backspace and such before that.
+  ///
+  /// Turning this off is for when you want a line of "cooked" input without
+  /// going all the way to [`shell_mode`](Curses::shell_mode).
+  pub fn set_cbreak(&mut self, on: bool) -> Result<(), &'static str> {
+    if on {
+      unsafe_call_result!("set_cbreak", cbreak())
+    } else {
+      unsafe_call_result!("set_cbreak", nocbreak())
+    }
+  }
+
+  /// Gets the user's attention with the terminal bell.
+  ///
+  /// What happens depends on the [alert
+  /// preference](Curses::set_alert_preference). By default this flashes the
+  /// screen, or beeps if the terminal can't flash.
+  pub fn alert(&mut self) -> Result<(), &'static str> {
+    match self.alert_preference {
+      AlertPreference::Auto => unsafe_call_result!("alert", flash())
+        .or_else(|_| unsafe_call_result!("alert", beep())),
+      AlertPreference::Audible => unsafe_call_result!("alert", beep()),
+      AlertPreference::Visual => unsafe_call_result!("alert", flash()),
+    }
+  }
+
+  /// Sets what [`alert`](Curses::alert) does.
+  ///
+  /// * The default is `Auto`.
+  /// * Curses itself may still fall back to the other kind of alert if the
+  ///   terminal can't do the one that was picked.
+  pub fn set_alert_preference(&mut self, pref: AlertPreference) {
+    self.alert_preference = pref;
+  }
+
+  /// Sets if newlines are translated on input and output.
+  ///
+  /// * When on, pressing Enter gives `Ascii(b'\n')` from
+  ///   [`poll_events`](Curses::poll_events), and curses can use a line feed to
+  ///   move the cursor down when it updates the screen.
+  /// * When off, pressing Enter gives `Ascii(b'\r')` instead, and output isn't
+  ///   translated.
+  /// * [`CursesKey::Enter`] is only for an Enter key that the terminal reports
+  ///   as a special key (`KEY_ENTER`, usually the keypad one), and this setting
+  ///   doesn't affect it.
+  /// * Initially this is on.
+  pub fn set_newline_translation(
+    &mut self, on: bool,
+  ) -> Result<(), &'static str> {
+    if on {
+      unsafe_call_result!("set_newline_translation", nl())
+    } else {
+      unsafe_call_result!("set_newline_translation", nonl())
+    }
+  }
+
+  /// Sets if an interrupt key (eg: Ctrl+C) should flush the output buffer.
+  ///
+  /// Flushing makes the interrupt feel faster, but then curses doesn't know
+  /// what's actually on the screen.
+  ///
+  /// * The default is inherited from the terminal driver.
+  pub fn set_interrupt_flush(&mut self, on: bool) -> Result<(), &'static str> {
+    unsafe_call_result!("set_interrupt_flush", intrflush(self.ptr, on))
+  }
+
+  /// Sets if input should be read as full 8-bit bytes rather than 7-bit.
+  ///
+  /// * The default depends on the terminal.
+  pub fn set_meta(&mut self, on: bool) -> Result<(), &'static str> {
+    unsafe_call_result!("set_meta", meta(self.ptr, on))
+  }
+
   /// Get the cursor's current row and column.
   pub fn get_cursor_position(&self) -> Position {
     let x = unsafe { getcurx(self.ptr) as u32 };
@@ -204,20 +826,120 @@ impl Curses {
     Position { x, y }
   }
 
+  /// Gets the position of the window's upper left corner on the screen.
+  ///
+  /// `yacurses` only uses the one window that covers the whole screen, so
+  /// right now this is always `(0,0)`. Cursor positions are relative to this
+  /// origin, so adding them gives the absolute screen position (eg: when
+  /// mixing in your own direct terminal output).
+  pub fn get_window_origin(&self) -> Position {
+    let x = unsafe { getbegx(self.ptr) as u32 };
+    let y = unsafe { getbegy(self.ptr) as u32 };
+    Position { x, y }
+  }
+
+  /// Converts a position relative to the whole screen into a position
+  /// relative to this window.
+  ///
+  /// Things like mouse events report screen positions. Right now the window
+  /// always covers the whole screen, so the output is the same as the input,
+  /// but code that goes through this won't need to change if that changes.
+  ///
+  /// * A screen position that's up or left of the
+  ///   [window origin](Curses::get_window_origin) is moved to the window's
+  ///   edge.
+  pub fn translate_screen_position(&self, screen: Position) -> Position {
+    let origin = self.get_window_origin();
+    Position {
+      x: screen.x.saturating_sub(origin.x),
+      y: screen.y.saturating_sub(origin.y),
+    }
+  }
+
   /// Get the size of the terminal.
   ///
   /// Cursor positions can range in `0..COUNT` in each dimension.
+  ///
+  /// If a [logical size](Curses::set_logical_size) is set, the size is limited
+  /// to that.
   pub fn get_terminal_size(&self) -> TerminalSize {
+    let physical = self.get_physical_terminal_size();
+    match self.logical_size {
+      Some(logical) => TerminalSize {
+        x_count: physical.x_count.min(logical.x_count),
+        y_count: physical.y_count.min(logical.y_count),
+      },
+      None => physical,
+    }
+  }
+
+  /// Get the actual size of the terminal, ignoring any
+  /// [logical size](Curses::set_logical_size).
+  pub fn get_physical_terminal_size(&self) -> TerminalSize {
     let x_count = unsafe { getmaxx(self.ptr) as u32 };
     let y_count = unsafe { getmaxy(self.ptr) as u32 };
     TerminalSize { x_count, y_count }
   }
 
+  /// Limits drawing to the upper left part of the terminal.
+  ///
+  /// This is a software fallback for when the terminal can't actually be
+  /// resized. While a logical size is set:
+  /// * [`get_terminal_size`](Curses::get_terminal_size) reports the logical
+  ///   size (or the physical size, if that's smaller).
+  /// * [`move_cursor`](Curses::move_cursor) errors on positions outside of the
+  ///   logical area.
+  /// * Printing stops at the right edge of the logical area instead of
+  ///   wrapping, and the drawing helpers (such as
+  ///   [`fill_rect`](Curses::fill_rect)) clip to the logical area.
+  ///
+  /// Newlines printed as text, scrolling, and clearing are still handled by
+  /// curses for the whole terminal.
+  ///
+  /// Use `None` to go back to using the whole terminal.
+  pub fn set_logical_size(&mut self, size: Option<TerminalSize>) {
+    self.logical_size = size;
+  }
+
   /// Move the cursor to the position given.
   pub fn move_cursor(&mut self, p: Position) -> Result<(), &'static str> {
+    if let Some(logical) = self.logical_size {
+      if p.x >= logical.x_count || p.y >= logical.y_count {
+        return Err("move_cursor");
+      }
+    }
     unsafe_call_result!("move_cursor", wmove(self.ptr, p.y as _, p.x as _))
   }
 
+  /// Moves the cursor to the position given, and returns where the cursor
+  /// actually ended up.
+  ///
+  /// Curses rejects positions that are off of the screen, so this is handy
+  /// when the terminal might have been resized since you computed the
+  /// position. An error means the cursor didn't move at all.
+  pub fn move_cursor_checked(
+    &mut self, p: Position,
+  ) -> Result<Position, &'static str> {
+    self.move_cursor(p).map_err(|_| "move_cursor_checked")?;
+    Ok(self.get_cursor_position())
+  }
+
+  /// How many cells are left on the cursor's row, within the physical screen
+  /// and the logical size.
+  fn row_room(&self) -> usize {
+    let width = self.get_physical_terminal_size().x_count;
+    let room = width.saturating_sub(self.get_cursor_position().x) as usize;
+    room.min(self.logical_room().unwrap_or(usize::MAX))
+  }
+
+  /// How many cells are left on the cursor's row before the right edge of the
+  /// logical size, if there is one.
+  fn logical_room(&self) -> Option<usize> {
+    self.logical_size.map(|logical| {
+      logical.x_count.saturating_sub(self.get_cursor_position().x) as usize
+    })
+  }
+
   /// Prints the character given, advancing the cursor.
   ///
   /// * Wraps to the next line if in the final col.
@@ -225,9 +947,36 @@ impl Curses {
   pub fn print_ch<C: Into<CursesGlyph>>(
     &mut self, c: C,
   ) -> Result<(), &'static str> {
+    if self.logical_room() == Some(0) {
+      return Ok(());
+    }
     unsafe_call_result!("print_ch", waddch(self.ptr, c.into().as_chtype()))
   }
 
+  /// Prints the character given without moving the cursor.
+  ///
+  /// This overwrites the cell under the cursor, unlike
+  /// [`insert_ch`](Curses::insert_ch) which pushes the rest of the line over.
+  pub fn overwrite_ch<C: Into<CursesGlyph>>(
+    &mut self, c: C,
+  ) -> Result<(), &'static str> {
+    let old = self.get_cursor_position();
+    if self.logical_room() == Some(0) {
+      return Ok(());
+    }
+    let ret = unsafe { waddch(self.ptr, c.into().as_chtype()) };
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    // In the bottom right cell the glyph is written even though the cursor
+    // can't advance, which isn't an error for us.
+    let size = self.get_physical_terminal_size();
+    let bottom_right = old.x + 1 == size.x_count && old.y + 1 == size.y_count;
+    if ret == ERR && !bottom_right {
+      Err("overwrite_ch")
+    } else {
+      Ok(())
+    }
+  }
+
   /// Prints the str given, advancing the cursor.
   ///
   /// This is identical to calling [`print_ch`](Curses::print_ch) on every byte
@@ -237,12 +986,217 @@ impl Curses {
   /// * Wraps to the next line if in the final col.
   /// * Will scroll the terminal if in the final row, if scrolling is enabled.
   pub fn print_str(&mut self, s: &str) -> Result<(), &'static str> {
+    let len = s.len().min(self.logical_room().unwrap_or(usize::MAX));
     unsafe_call_result!(
       "print_str",
-      waddnstr(self.ptr, s.as_ptr().cast(), s.len().try_into().unwrap())
+      waddnstr(self.ptr, s.as_ptr().cast(), len.try_into().unwrap())
+    )
+  }
+
+  /// Prints at most `max_cells` bytes of the str given, advancing the cursor.
+  ///
+  /// Like with [`print_str`](Curses::print_str), each byte is one cell, so
+  /// this is meant for ascii text.
+  ///
+  /// * The text is also cut off at the right edge of the terminal, so it never
+  ///   wraps onto the next line or scrolls the terminal.
+  /// * If the text fills the final col the cursor stays in the final col.
+  pub fn print_str_truncated(
+    &mut self, s: &str, max_cells: u32,
+  ) -> Result<(), &'static str> {
+    let len = s.len().min(max_cells as usize).min(self.row_room());
+    if self.print_bytes_unwrapped(&s.as_bytes()[..len]) {
+      Ok(())
+    } else {
+      Err("print_str_truncated")
+    }
+  }
+
+  /// Prints bytes that fit on the cursor's row without wrapping.
+  ///
+  /// A byte in the final col would make the cursor wrap (or scroll the
+  /// terminal, in the final row), so that one is put in with `winsch` instead,
+  /// which doesn't move the cursor.
+  ///
+  /// Returns `false` on an actual error.
+  fn print_bytes_unwrapped(&mut self, bytes: &[u8]) -> bool {
+    // `waddnstr` would stop at a 0 byte, so the last byte is the one before.
+    let bytes = bytes.split(|&b| b == 0).next().unwrap_or(&[]);
+    let x = self.get_cursor_position().x as usize;
+    let width = self.get_physical_terminal_size().x_count as usize;
+    let (head, last) = match bytes.split_last() {
+      Some((&last, head)) if x + bytes.len() >= width => (head, Some(last)),
+      _ => (bytes, None),
+    };
+    let head_ok = ERR
+      != unsafe {
+        waddnstr(self.ptr, head.as_ptr().cast(), head.len().try_into().unwrap())
+      };
+    let last_ok = match last {
+      Some(b) => ERR != unsafe { winsch(self.ptr, b as chtype) },
+      None => true,
+    };
+    head_ok && last_ok
+  }
+
+  /// Works out what column the cursor would end up in if the str given was
+  /// printed starting at `start_col`.
+  ///
+  /// This follows what curses does with each byte:
+  ///
+  /// * A tab moves to the next tab stop, using the current tab size.
+  /// * A newline or carriage return goes back to column 0.
+  /// * A backspace moves back one column (but not past column 0).
+  /// * Other control bytes are shown as `^X`, so they take two cells.
+  /// * Every other byte takes one cell, same as with
+  ///   [`print_str`](Curses::print_str).
+  ///
+  /// Wrapping at the right edge of the screen isn't accounted for, so the
+  /// output can be past the last column.
+  pub fn measure_str(&self, s: &str, start_col: u32) -> u32 {
+    let tab = unsafe { TABSIZE }.max(1) as u32;
+    s.bytes().fold(start_col, |col, b| match b {
+      b'\t' => (col / tab).saturating_add(1).saturating_mul(tab),
+      b'\n' | b'\r' => 0,
+      0x08 => col.saturating_sub(1),
+      0x00..=0x1F | 0x7F => col.saturating_add(2),
+      _ => col.saturating_add(1),
+    })
+  }
+
+  /// Prints the str given with the attributes and color pair given, then
+  /// puts back the previous attributes and color pair.
+  ///
+  /// Otherwise this works like [`print_str`](Curses::print_str). The old
+  /// render state is restored even if printing fails.
+  pub fn print_styled(
+    &mut self, s: &str, attr: Attributes, pair: Option<ColorPair>,
+  ) -> Result<(), &'static str> {
+    let (old_attr, old_pair) =
+      self.get_render_state().map_err(|_| "print_styled")?;
+    self.set_render_state(attr, pair).map_err(|_| "print_styled")?;
+    let out = self.print_str(s).map_err(|_| "print_styled");
+    self.set_render_state(old_attr, old_pair).map_err(|_| "print_styled")?;
+    out
+  }
+
+  /// Prints a styled str at a position without disturbing anything else.
+  ///
+  /// The text is printed with the attributes and color pair given, and is
+  /// cut off at the right edge of the screen like with
+  /// [`print_str_truncated`](Curses::print_str_truncated), so it never wraps
+  /// or scrolls the terminal, even in the bottom right cell. Afterwards the
+  /// cursor position, attributes, color pair, and background are all put
+  /// back with [`restore_state`](Curses::restore_state), even if printing
+  /// fails.
+  ///
+  /// * Errors if `p` isn't on the screen, in which case nothing is printed.
+  pub fn stamp(
+    &mut self, p: Position, s: &str, attr: Attributes, pair: Option<ColorPair>,
+  ) -> Result<(), &'static str> {
+    let state = self.save_state();
+    self.move_cursor(p).map_err(|_| "stamp")?;
+    let out = self
+      .set_render_state(attr, pair)
+      .and_then(|_| self.print_str_truncated(s, u32::MAX))
+      .map_err(|_| "stamp");
+    self.restore_state(&state).map_err(|_| "stamp")?;
+    out
+  }
+
+  /// Prints the bytes given, advancing the cursor.
+  ///
+  /// This is identical to calling [`print_ch`](Curses::print_ch) on every byte
+  /// in `bytes`, up to the first 0 byte. Non-ascii bytes will probably show up
+  /// as garbage on the screen.
+  ///
+  /// * Wraps to the next line if in the final col.
+  /// * Will scroll the terminal if in the final row, if scrolling is enabled.
+  pub fn print_bytes(&mut self, bytes: &[u8]) -> Result<(), &'static str> {
+    let len = bytes.len().min(self.logical_room().unwrap_or(usize::MAX));
+    unsafe_call_result!(
+      "print_bytes",
+      waddnstr(self.ptr, bytes.as_ptr().cast(), len.try_into().unwrap())
     )
   }
 
+  /// Prints the str given centered on the given row.
+  ///
+  /// * If the str is wider than the screen, it's printed from the left edge
+  ///   and the end is cut off instead of wrapping.
+  /// * The cursor is left after the printed text.
+  pub fn print_centered(
+    &mut self, y: u32, s: &str,
+  ) -> Result<(), &'static str> {
+    let width = self.get_terminal_size().x_count as usize;
+    let bytes = &s.as_bytes()[..s.len().min(width)];
+    let x = ((width - bytes.len()) / 2) as u32;
+    self.move_cursor(Position { x, y }).map_err(|_| "print_centered")?;
+    self.print_bytes(bytes).map_err(|_| "print_centered")
+  }
+
+  /// Prints the str given aligned within a field `width` cells wide, starting
+  /// at the cursor.
+  ///
+  /// * Each byte is one cell, so this is meant for ascii text.
+  /// * The rest of the field is filled with spaces.
+  /// * If the str is wider than the field, the end is cut off.
+  /// * Like with [`print_str_truncated`](Curses::print_str_truncated), the
+  ///   field is cut off at the right edge of the screen rather than wrapping
+  ///   or scrolling.
+  /// * The cursor is left after the field, or in the final col if the field
+  ///   reaches it.
+  pub fn print_padded(
+    &mut self, s: &str, width: u32, align: Align,
+  ) -> Result<(), &'static str> {
+    let width = width as usize;
+    let text = &s.as_bytes()[..s.len().min(width)];
+    let spare = width - text.len();
+    let left = match align {
+      Align::Left => 0,
+      Align::Center => spare / 2,
+      Align::Right => spare,
+    };
+    // Only the part of the field that fits on the row is built.
+    let len = width.min(self.row_room());
+    let mut field = vec![b' '; len];
+    let start = left.min(len);
+    let end = (left + text.len()).min(len);
+    field[start..end].copy_from_slice(&text[..end - start]);
+    if self.print_bytes_unwrapped(&field) {
+      Ok(())
+    } else {
+      Err("print_padded")
+    }
+  }
+
+  /// Prints the str given, advancing the cursor, and returns how many cells
+  /// the cursor advanced.
+  ///
+  /// This works like [`print_str`](Curses::print_str), but the cursor position
+  /// is checked before and after the print. Wrapping to later lines counts the
+  /// full width of each line that was wrapped past.
+  ///
+  /// * If printing stopped early at the edge of the screen, this is still `Ok`
+  ///   and the count only includes the cells actually advanced. It's only an
+  ///   error if the print failed without advancing at all.
+  /// * If the screen scrolled, the count will be lower than the true number of
+  ///   cells printed.
+  pub fn print_str_counted(&mut self, s: &str) -> Result<u32, &'static str> {
+    let before = self.get_cursor_position();
+    let result = self.print_str(s);
+    let after = self.get_cursor_position();
+    let width = self.get_physical_terminal_size().x_count;
+    let before_index = before.y * width + before.x;
+    let after_index = after.y * width + after.x;
+    let advanced = after_index.saturating_sub(before_index);
+    if result.is_err() && advanced == 0 {
+      Err("print_str_counted")
+    } else {
+      Ok(advanced)
+    }
+  }
+
   /// Inserts the given character under the cursor.
   ///
   /// * The cursor doesn't move.
@@ -263,17 +1217,404 @@ impl Curses {
     unsafe_call_result!("delete_ch", wdelch(self.ptr))
   }
 
-  /// Copies the slice of glyphs starting from the cursor position.
+  /// Prints the glyph given `n` times in a row, advancing the cursor.
+  ///
+  /// This is handy for things like progress bars, and it's a single call to
+  /// curses rather than `n` calls to [`print_ch`](Curses::print_ch).
+  ///
+  /// * Does not wrap the content to the next line, the glyphs that would go
+  ///   past the right edge of the screen are skipped.
+  /// * The cursor is left after the last glyph, or in the final col if the row
+  ///   was filled.
+  pub fn print_ch_repeated<C: Into<CursesGlyph>>(
+    &mut self, c: C, n: u32,
+  ) -> Result<(), &'static str> {
+    let count = (n as usize).min(self.row_room());
+    if count == 0 {
+      return Ok(());
+    }
+    let glyphs = vec![c.into().as_chtype(); count];
+    unsafe_call_result!(
+      "print_ch_repeated",
+      waddchnstr(self.ptr, glyphs.as_ptr(), count.try_into().unwrap())
+    )?;
+    let p = self.get_cursor_position();
+    let last_col = self.get_physical_terminal_size().x_count.saturating_sub(1);
+    let x = (p.x + count as u32).min(last_col);
+    unsafe_call_result!("print_ch_repeated", wmove(self.ptr, p.y as _, x as _))
+  }
+
+  /// Copies the slice of glyphs starting from the cursor position.
   ///
   /// * Does not advance the cursor.
   /// * Does not wrap the content to the next line.
   pub fn copy_glyphs(&mut self, s: &[CursesGlyph]) -> Result<(), &'static str> {
+    let len = s.len().min(self.logical_room().unwrap_or(usize::MAX));
     unsafe_call_result!(
       "copy_glyphs",
-      waddchnstr(self.ptr, s.as_ptr().cast(), s.len().try_into().unwrap())
+      waddchnstr(self.ptr, s.as_ptr().cast(), len.try_into().unwrap())
+    )
+  }
+
+  /// Copies as much of the slice of glyphs as fits on the cursor's row, and
+  /// returns how many glyphs were copied.
+  ///
+  /// Otherwise this works like [`copy_glyphs`](Curses::copy_glyphs).
+  pub fn copy_glyphs_clipped(
+    &mut self, s: &[CursesGlyph],
+  ) -> Result<usize, &'static str> {
+    let count = s.len().min(self.row_room());
+    if count == 0 {
+      return Ok(0);
+    }
+    unsafe_call_result!(
+      "copy_glyphs_clipped",
+      waddchnstr(self.ptr, s.as_ptr().cast(), count.try_into().unwrap())
+    )
+    .map(|_| count)
+  }
+
+  /// Draws a rectangle of glyphs with the top left corner at the position
+  /// given.
+  ///
+  /// The glyphs are in row-major order, `width` glyphs per row. Any partial
+  /// row at the end is also drawn.
+  ///
+  /// * Glyphs with an `ascii` value equal to `transparent` are skipped, so
+  ///   whatever was on the screen before stays there.
+  /// * Glyphs that would go off the screen are skipped.
+  /// * The cursor doesn't move.
+  pub fn blit(
+    &mut self, top_left: Position, width: u32, glyphs: &[CursesGlyph],
+    transparent: Option<u8>,
+  ) -> Result<(), &'static str> {
+    if width == 0 {
+      return Ok(());
+    }
+    let old = self.get_cursor_position();
+    let size = self.get_terminal_size();
+    let TerminalSize { x_count, y_count } = size;
+    let mut out = Ok(());
+    for (row, row_glyphs) in glyphs.chunks(width as usize).enumerate() {
+      let y = top_left.y.saturating_add(row as u32);
+      if y >= y_count {
+        break;
+      }
+      for (col, glyph) in row_glyphs.iter().enumerate() {
+        let x = top_left.x.saturating_add(col as u32);
+        if x >= x_count {
+          break;
+        }
+        if Some(glyph.ascii) == transparent {
+          continue;
+        }
+        if !self.put_glyph_clipped(Position { x, y }, *glyph, size) {
+          out = Err("blit");
+        }
+      }
+    }
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    out
+  }
+
+  /// Draws each glyph at its paired position.
+  ///
+  /// This is for scattered updates, where [`copy_glyphs`](Curses::copy_glyphs)
+  /// only handles a single run of glyphs.
+  ///
+  /// * Cells that would go off the screen are skipped.
+  /// * Afterwards the cursor is on the last cell drawn. If no cell was drawn
+  ///   the cursor doesn't move.
+  pub fn draw_cells(
+    &mut self, cells: &[(Position, CursesGlyph)],
+  ) -> Result<(), &'static str> {
+    let screen = self.get_terminal_size();
+    let mut last = None;
+    let mut out = Ok(());
+    for &(p, glyph) in cells {
+      if p.x >= screen.x_count || p.y >= screen.y_count {
+        continue;
+      }
+      if !self.put_glyph_clipped(p, glyph, screen) {
+        out = Err("draw_cells");
+      }
+      last = Some(p);
+    }
+    if let Some(p) = last {
+      unsafe_always_ok!(wmove(self.ptr, p.y as _, p.x as _));
+    }
+    out
+  }
+
+  /// Changes the attributes and color pair of every cell in a rect.
+  ///
+  /// The characters in the cells are kept as they are, so this is good for
+  /// highlighting a selection that spans several rows.
+  ///
+  /// * The rect is clipped to the screen.
+  /// * The cursor position is not changed.
+  pub fn highlight_rect(
+    &mut self, rect: Rect, attr: Attributes, opt_pair: Option<ColorPair>,
+  ) -> Result<(), &'static str> {
+    let old = self.get_cursor_position();
+    let clipped = rect.clamp_to(self.get_terminal_size());
+    // The color pair is passed separately, so it's left out of the word.
+    let attr: attr_t = attr.to_attr_word(None);
+    let p = opt_pair.map(|cp| cp.get()).unwrap_or(0).into();
+    let mut out = Ok(());
+    for y in clipped.origin.y..clipped.y_end() {
+      if unsafe_call_result!(
+        "highlight_rect",
+        mvwchgat(
+          self.ptr,
+          y as _,
+          clipped.origin.x as _,
+          clipped.size.x_count as _,
+          attr,
+          p,
+          core::ptr::null()
+        )
+      )
+      .is_err()
+      {
+        out = Err("highlight_rect");
+      }
+    }
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    out
+  }
+
+  /// Fills a rectangle of the screen with the glyph given.
+  ///
+  /// * Cells that would go off the screen are skipped.
+  /// * The cursor doesn't move.
+  pub fn fill_rect<C: Into<CursesGlyph>>(
+    &mut self, rect: Rect, c: C,
+  ) -> Result<(), &'static str> {
+    let glyph = c.into();
+    let old = self.get_cursor_position();
+    let screen = self.get_terminal_size();
+    let clipped = rect.clamp_to(screen);
+    let mut out = Ok(());
+    for y in clipped.origin.y..clipped.y_end() {
+      for x in clipped.origin.x..clipped.x_end() {
+        if !self.put_glyph_clipped(Position { x, y }, glyph, screen) {
+          out = Err("fill_rect");
+        }
+      }
+    }
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    out
+  }
+
+  /// Draws a horizontal gauge (such as a progress bar) that fills the rect
+  /// given.
+  ///
+  /// The leftmost `fraction` of the rect's width is drawn with `filled`, and
+  /// the rest is drawn with `empty`.
+  ///
+  /// * `fraction` is clamped to `0.0 ..= 1.0`, and the filled width is rounded
+  ///   to the nearest cell.
+  /// * Cells that would go off the screen are skipped.
+  /// * The cursor doesn't move.
+  pub fn draw_gauge(
+    &mut self, rect: Rect, fraction: f32, filled: CursesGlyph,
+    empty: CursesGlyph,
+  ) -> Result<(), &'static str> {
+    let width = rect.size.x_count;
+    // Float to int `as` casts saturate, and NaN becomes 0.
+    let filled_width = ((fraction * width as f32).round() as u32).min(width);
+    let (filled_size, empty_size) = (
+      TerminalSize { x_count: filled_width, y_count: rect.size.y_count },
+      TerminalSize {
+        x_count: width - filled_width,
+        y_count: rect.size.y_count,
+      },
+    );
+    let empty_origin = Position {
+      x: rect.origin.x.saturating_add(filled_width),
+      y: rect.origin.y,
+    };
+    let a = self.fill_rect(Rect::new(rect.origin, filled_size), filled);
+    let b = self.fill_rect(Rect::new(empty_origin, empty_size), empty);
+    a.and(b).map_err(|_| "draw_gauge")
+  }
+
+  /// Draws a box outline using the ACS line and corner glyphs.
+  ///
+  /// The outline takes up the outermost cells of the rectangle given, and the
+  /// inside is left alone.
+  ///
+  /// * Cells that would go off the screen are skipped.
+  /// * The cursor doesn't move.
+  pub fn draw_rect_border(&mut self, rect: Rect) -> Result<(), &'static str> {
+    if rect.is_empty() {
+      return Ok(());
+    }
+    let old = self.get_cursor_position();
+    let screen = self.get_terminal_size();
+    let left = rect.origin.x;
+    let top = rect.origin.y;
+    let right = rect.x_end() - 1;
+    let bottom = rect.y_end() - 1;
+    let (hline, vline) = (self.acs_hline(), self.acs_vline());
+    let mut ok = true;
+    for x in left..=right {
+      ok &= self.put_glyph_clipped(Position { x, y: top }, hline, screen);
+      ok &= self.put_glyph_clipped(Position { x, y: bottom }, hline, screen);
+    }
+    for y in top..=bottom {
+      ok &= self.put_glyph_clipped(Position { x: left, y }, vline, screen);
+      ok &= self.put_glyph_clipped(Position { x: right, y }, vline, screen);
+    }
+    let corners = [
+      (Position { x: left, y: top }, self.acs_ulcorner()),
+      (Position { x: right, y: top }, self.acs_urcorner()),
+      (Position { x: left, y: bottom }, self.acs_llcorner()),
+      (Position { x: right, y: bottom }, self.acs_lrcorner()),
+    ];
+    for (p, glyph) in corners.iter().copied() {
+      ok &= self.put_glyph_clipped(p, glyph, screen);
+    }
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    if ok {
+      Ok(())
+    } else {
+      Err("draw_rect_border")
+    }
+  }
+
+  /// Draws a border around the edge of the whole screen, picking the glyph for
+  /// each side and corner separately.
+  ///
+  /// * `None` uses the curses default for that part, which is the matching
+  ///   ACS line or corner glyph.
+  /// * To leave a part out of the frame, pass a blank glyph such as
+  ///   [`CursesGlyph::SPACE`].
+  /// * The cursor doesn't move.
+  #[allow(clippy::too_many_arguments)]
+  pub fn draw_border_parts(
+    &mut self, left: Option<CursesGlyph>, right: Option<CursesGlyph>,
+    top: Option<CursesGlyph>, bottom: Option<CursesGlyph>,
+    top_left: Option<CursesGlyph>, top_right: Option<CursesGlyph>,
+    bottom_left: Option<CursesGlyph>, bottom_right: Option<CursesGlyph>,
+  ) -> Result<(), &'static str> {
+    let ch =
+      |g: Option<CursesGlyph>| g.map(CursesGlyph::as_chtype).unwrap_or(0);
+    unsafe_call_result!(
+      "draw_border_parts",
+      wborder(
+        self.ptr,
+        ch(left),
+        ch(right),
+        ch(top),
+        ch(bottom),
+        ch(top_left),
+        ch(top_right),
+        ch(bottom_left),
+        ch(bottom_right)
+      )
     )
   }
 
+  /// Writes a glyph at the given position with `mvwaddch`, doing nothing if
+  /// the position is off of the screen.
+  ///
+  /// Returns `false` on an actual error.
+  fn put_glyph_clipped(
+    &mut self, p: Position, glyph: CursesGlyph, screen: TerminalSize,
+  ) -> bool {
+    if p.x >= screen.x_count || p.y >= screen.y_count {
+      return true;
+    }
+    let ret =
+      unsafe { mvwaddch(self.ptr, p.y as _, p.x as _, glyph.as_chtype()) };
+    // Writing to the bottom right cell "fails" when the cursor can't wrap
+    // afterwards, but the glyph is still written.
+    let physical = self.get_physical_terminal_size();
+    let bottom_right =
+      p.x + 1 == physical.x_count && p.y + 1 == physical.y_count;
+    ret != ERR || bottom_right
+  }
+
+  /// Reads back up to `max` characters of text from the start of the given
+  /// row.
+  ///
+  /// * Attributes and colors are stripped, you just get the text.
+  /// * A `max` wider than the screen only reads the whole row.
+  /// * The cursor doesn't move.
+  pub fn read_line(&self, y: u32, max: usize) -> Result<String, &'static str> {
+    // The line starts at column 0, so it can't be longer than the row. This
+    // also keeps the length small enough for curses.
+    let max = max.min(self.get_physical_terminal_size().x_count as usize);
+    let old = self.get_cursor_position();
+    // curses also writes a terminating 0 after the text it reads.
+    let mut buf: Vec<u8> = vec![0; max + 1];
+    let count = unsafe {
+      mvwinnstr(self.ptr, y as _, 0, buf.as_mut_ptr().cast(), max as _)
+    };
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    if count == ERR {
+      Err("read_line")
+    } else {
+      buf.truncate(count as usize);
+      Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+  }
+
+  /// Reads the glyph in a single cell, including its attributes and color
+  /// pair.
+  ///
+  /// This is the reading version of drawing a glyph somewhere. The cursor
+  /// doesn't move.
+  ///
+  /// * Errors if the position isn't on the screen.
+  pub fn inspect(&self, p: Position) -> Result<CursesGlyph, &'static str> {
+    let size = self.get_physical_terminal_size();
+    if p.x >= size.x_count || p.y >= size.y_count {
+      return Err("inspect");
+    }
+    let old = self.get_cursor_position();
+    let cht = unsafe { mvwinch(self.ptr, p.y as _, p.x as _) };
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    Ok(CursesGlyph::from(cht))
+  }
+
+  /// Reads back a run of glyphs (with their color pair and attributes),
+  /// starting at the position given, and returns how many were read.
+  ///
+  /// This is the reverse of [`copy_glyphs`](Curses::copy_glyphs), so you can
+  /// save part of the screen and restore it later.
+  ///
+  /// * Reading stops at the end of the row, or when `out` is full.
+  /// * The cursor doesn't move.
+  pub fn read_glyphs(
+    &self, p: Position, out: &mut [CursesGlyph],
+  ) -> Result<usize, &'static str> {
+    let old = self.get_cursor_position();
+    // curses also writes a terminating 0 after the glyphs it reads.
+    let mut buf: Vec<chtype> = vec![0; out.len() + 1];
+    let count = unsafe {
+      mvwinchnstr(
+        self.ptr,
+        p.y as _,
+        p.x as _,
+        buf.as_mut_ptr(),
+        out.len().try_into().unwrap_or(i32::MAX),
+      )
+    };
+    unsafe_always_ok!(wmove(self.ptr, old.y as _, old.x as _));
+    if count == ERR {
+      Err("read_glyphs")
+    } else {
+      let count = (count as usize).min(out.len());
+      for (o, cht) in out.iter_mut().zip(buf.iter().copied()).take(count) {
+        *o = CursesGlyph::from(cht);
+      }
+      Ok(count)
+    }
+  }
+
   /// Clears the entire screen and moves the cursor to `(0,0)`.
   ///
   /// This can have somewhat poor performance. If you're just going to overwrite
@@ -282,6 +1623,23 @@ impl Curses {
     unsafe_call_result!("clear", wclear(self.ptr))
   }
 
+  /// Writes the glyph given to every cell of the screen and moves the cursor
+  /// to `(0,0)`.
+  ///
+  /// Unlike changing the background and then clearing, this leaves the
+  /// background glyph alone.
+  pub fn fill<C: Into<CursesGlyph>>(
+    &mut self, c: C,
+  ) -> Result<(), &'static str> {
+    let TerminalSize { x_count, y_count } = self.get_terminal_size();
+    let row = vec![c.into(); x_count as usize];
+    for y in 0..y_count {
+      unsafe_call_result!("fill", wmove(self.ptr, y as _, 0))?;
+      self.copy_glyphs(&row).map_err(|_| "fill")?;
+    }
+    unsafe_call_result!("fill", wmove(self.ptr, 0, 0))
+  }
+
   /// Set the given attribute bits to be on or off.
   pub fn set_attributes(
     &mut self, attr: Attributes, on: bool,
@@ -294,17 +1652,325 @@ impl Curses {
     }
   }
 
+  /// Turns on the given attribute bits, and also sets the color pair if one
+  /// is given.
+  ///
+  /// Unlike [`set_attributes`](Curses::set_attributes), this can also change
+  /// the color pair. With `None` the color pair is left alone.
+  pub fn attr_on(
+    &mut self, attr: Attributes, pair: Option<ColorPair>,
+  ) -> Result<(), &'static str> {
+    let word: attr_t = attr.to_attr_word(pair);
+    unsafe_call_result!(
+      "attr_on",
+      wattr_on(self.ptr, word, core::ptr::null_mut())
+    )
+  }
+
+  /// Turns off the given attribute bits, and also goes back to the default
+  /// color pair if a color pair is given.
+  ///
+  /// With `None` the color pair is left alone.
+  pub fn attr_off(
+    &mut self, attr: Attributes, pair: Option<ColorPair>,
+  ) -> Result<(), &'static str> {
+    let word: attr_t = attr.to_attr_word(pair);
+    unsafe_call_result!(
+      "attr_off",
+      wattr_off(self.ptr, word, core::ptr::null_mut())
+    )
+  }
+
+  /// Turns on the `add` attributes and turns off the `remove` attributes, in
+  /// one step.
+  ///
+  /// Attributes in neither set keep their current state, and the color pair
+  /// is kept. If a bit is in both sets it ends up on.
+  pub fn modify_attributes(
+    &mut self, add: Attributes, remove: Attributes,
+  ) -> Result<(), &'static str> {
+    let (attr, pair) =
+      self.get_render_state().map_err(|_| "modify_attributes")?;
+    let new_attr = Attributes((attr.0 & !remove.0) | add.0);
+    self.set_render_state(new_attr, pair).map_err(|_| "modify_attributes")
+  }
+
+  /// Sets the attributes and color pair for all newly printed glyphs in one
+  /// call.
+  ///
+  /// Unlike [`set_attributes`](Curses::set_attributes), this *replaces* the
+  /// attributes: bits not in `attr` are turned off.
+  pub fn set_render_state(
+    &mut self, attr: Attributes, opt_pair: Option<ColorPair>,
+  ) -> Result<(), &'static str> {
+    // The color pair is passed separately, so it's left out of the word.
+    let attr: attr_t = attr.to_attr_word(None);
+    let p = opt_pair.map(|cp| cp.get()).unwrap_or(0).into();
+    unsafe_call_result!(
+      "set_render_state",
+      wattr_set(self.ptr, attr, p, core::ptr::null_mut())
+    )
+  }
+
+  /// Gets the attributes that the terminal says it supports.
+  ///
+  /// Unsupported attributes are silently ignored when drawing, so you can
+  /// check this to pick a fallback (eg: use `REVERSE` if `ITALIC` isn't
+  /// available).
+  ///
+  /// * This is based on the terminal's description, which isn't always
+  ///   accurate for terminal emulators.
+  /// * pdcurses reports what its console backend can do.
+  pub fn supported_attributes(&self) -> Attributes {
+    Attributes::from_attr_word(unsafe { termattrs() }).0
+  }
+
+  /// Gets the terminal's escape sequence for switching to exactly the
+  /// attributes given, based on its terminfo entry.
+  ///
+  /// This is for programs that write their own output to part of the screen
+  /// and want it to match what curses would draw. The sequence turns off any
+  /// attributes not given, as well as setting the ones given.
+  ///
+  /// * Gives `None` if the terminal can't do one of the attributes.
+  /// * Color is not included.
+  /// * On Windows this is always `None`, pdcurses doesn't use terminfo.
+  pub fn attribute_escape(&self, attr: Attributes) -> Option<String> {
+    #[cfg(unix)]
+    {
+      use std::{ffi::CStr, os::raw::c_long};
+      let cap = |name: &str| self.terminfo_string(name).map(CStr::to_bytes);
+      let has = |a: Attributes| (attr.0 & a.0) != 0;
+      let mut out = Vec::new();
+      if let Some(sgr) = self.terminfo_string("sgr") {
+        let p = |a: Attributes| has(a) as c_long;
+        let s = unsafe {
+          tparm(
+            sgr.as_ptr(),
+            p(Attributes::STANDOUT),
+            p(Attributes::UNDERLINE),
+            p(Attributes::REVERSE),
+            p(Attributes::BLINK),
+            p(Attributes::DIM),
+            p(Attributes::BOLD),
+            p(Attributes::INVIS),
+            0 as c_long,
+            p(Attributes::ALT_CHAR_SET),
+          )
+        };
+        if s.is_null() {
+          return None;
+        }
+        out.extend_from_slice(unsafe { CStr::from_ptr(s) }.to_bytes());
+      } else {
+        // Without `sgr`, reset everything and then turn on each attribute.
+        out.extend_from_slice(cap("sgr0")?);
+        let caps: [(Attributes, &str); 8] = [
+          (Attributes::STANDOUT, "smso"),
+          (Attributes::UNDERLINE, "smul"),
+          (Attributes::REVERSE, "rev"),
+          (Attributes::BLINK, "blink"),
+          (Attributes::DIM, "dim"),
+          (Attributes::BOLD, "bold"),
+          (Attributes::INVIS, "invis"),
+          (Attributes::ALT_CHAR_SET, "smacs"),
+        ];
+        for (a, name) in caps.iter() {
+          if has(*a) {
+            out.extend_from_slice(cap(name)?);
+          }
+        }
+      }
+      if has(Attributes::ITALIC) {
+        out.extend_from_slice(cap("sitm")?);
+      }
+      // Drop terminfo padding specs (eg: `$<2>`), those are instructions for
+      // curses and not meant to be sent to the terminal.
+      let mut text = String::from_utf8(out).ok()?;
+      while let Some(start) = text.find("$<") {
+        match text[start..].find('>') {
+          Some(len) => text.replace_range(start..=start + len, ""),
+          None => break,
+        }
+      }
+      Some(text)
+    }
+    #[cfg(windows)]
+    {
+      let _ = attr;
+      None
+    }
+  }
+
+  /// Looks up a string capability of the terminal in its terminfo entry.
+  ///
+  /// Gives `None` if the terminal doesn't have the capability, or if it isn't
+  /// a string capability.
+  #[cfg(unix)]
+  fn terminfo_string(&self, cap: &str) -> Option<&std::ffi::CStr> {
+    let cap = CString::new(cap).ok()?;
+    let p = unsafe { tigetstr(cap.as_ptr()) };
+    // A missing capability is null, and one that isn't a string is -1.
+    if p.is_null() || p as isize == -1 {
+      None
+    } else {
+      Some(unsafe { std::ffi::CStr::from_ptr(p) })
+    }
+  }
+
+  /// Gets the attribute bits that are currently on.
+  pub fn get_attributes(&self) -> Result<Attributes, &'static str> {
+    self.get_render_state().map(|(attr, _)| attr).map_err(|_| "get_attributes")
+  }
+
+  /// Gets the attributes and color pair used for newly printed glyphs.
+  ///
+  /// This is the reverse of [`set_render_state`](Curses::set_render_state).
+  pub fn get_render_state(
+    &self,
+  ) -> Result<(Attributes, Option<ColorPair>), &'static str> {
+    let mut attr: attr_t = 0;
+    let mut pair: i16 = 0;
+    unsafe_call_result!(
+      "get_render_state",
+      wattr_get(self.ptr, &mut attr, &mut pair, core::ptr::null_mut())
+    )
+    .map(|_| {
+      let attr = Attributes::from_attr_word(attr as u32).0;
+      (attr, ColorPair::new(pair as u8))
+    })
+  }
+
+  /// Saves the cursor position, attributes, color pair, and background, so
+  /// that they can be put back later with
+  /// [`restore_state`](Curses::restore_state).
+  ///
+  /// This lets a bit of drawing code change whatever it wants and then clean
+  /// up after itself.
+  pub fn save_state(&self) -> CursesState {
+    let (attributes, opt_color_pair) =
+      self.get_render_state().unwrap_or((Attributes(0), None));
+    CursesState {
+      position: self.get_cursor_position(),
+      attributes,
+      opt_color_pair,
+      background: self.get_background(),
+    }
+  }
+
+  /// Puts back state saved with [`save_state`](Curses::save_state).
+  ///
+  /// * The background is restored without changing any existing cells (like
+  ///   [`set_background_future`](Curses::set_background_future)). A
+  ///   background glyph with an `ascii` of 0 is stored by curses as a space,
+  ///   which draws the same.
+  /// * Errors if the saved cursor position is no longer on the screen, but
+  ///   everything else is still restored.
+  pub fn restore_state(
+    &mut self, state: &CursesState,
+  ) -> Result<(), &'static str> {
+    self.set_background_future(state.background);
+    let render = self.set_render_state(state.attributes, state.opt_color_pair);
+    let p = state.position;
+    let moved =
+      unsafe_call_result!("restore_state", wmove(self.ptr, p.y as _, p.x as _));
+    render.and(moved).map_err(|_| "restore_state")
+  }
+
+  /// Turns on the given attribute bits, runs the closure, then turns back off
+  /// any of those bits that weren't already on.
+  ///
+  /// This way you can't forget to turn off an attribute on an early return.
+  pub fn with_attributes<R>(
+    &mut self, attr: Attributes, f: impl FnOnce(&mut Curses) -> R,
+  ) -> Result<R, &'static str> {
+    let prior = self.get_attributes()?;
+    self.set_attributes(attr, true)?;
+    let out = f(self);
+    self.set_attributes(Attributes(attr.0 & !prior.0), false)?;
+    Ok(out)
+  }
+
   /// Attempts to change the terminal size to a new size.
   ///
   /// In many contexts the terminal size cannot change. Your program should
   /// tolerate that this will fail under reasonable conditions.
+  ///
+  /// If curses rejects the resize but the new size fits inside the current
+  /// terminal, the new size is set as the
+  /// [logical size](Curses::set_logical_size) instead and this returns `Ok`.
+  /// A successful resize clears any logical size.
   pub fn set_terminal_size(
     &mut self, size: TerminalSize,
   ) -> Result<(), &'static str> {
-    unsafe_call_result!(
+    let result = unsafe_call_result!(
       "resize_term",
       resize_term(size.y_count as _, size.x_count as _)
-    )
+    );
+    match result {
+      Ok(()) => {
+        self.logical_size = None;
+        Ok(())
+      }
+      Err(e) => {
+        let physical = self.get_physical_terminal_size();
+        if size.x_count <= physical.x_count && size.y_count <= physical.y_count
+        {
+          self.logical_size = Some(size);
+          Ok(())
+        } else {
+          Err(e)
+        }
+      }
+    }
+  }
+
+  /// Attempts to change the terminal size to a new size.
+  ///
+  /// This is the same as [`set_terminal_size`](Curses::set_terminal_size).
+  ///
+  /// * This only changes the size that curses *thinks* the terminal is. Most
+  ///   terminal emulators (and all ssh sessions) don't let a program resize the
+  ///   actual window, so in practice this is often a no-op or an error.
+  /// * Check [`get_terminal_size`](Curses::get_terminal_size) afterwards
+  ///   instead of assuming the size changed.
+  pub fn resize_to(&mut self, size: TerminalSize) -> Result<(), &'static str> {
+    self.set_terminal_size(size).map_err(|_| "resize_to")
+  }
+
+  /// Syncs curses with the terminal's actual size, and returns that size.
+  ///
+  /// [`poll_events`](Curses::poll_events) already does this when it gets a
+  /// resize event. Call this yourself if you might have missed that event
+  /// (eg: you flushed the event queue).
+  pub fn handle_resize(&mut self) -> TerminalSize {
+    // This can only fail on allocation failure, in which case we just report
+    // whatever size curses is still using.
+    let _ = unsafe_call_result!("handle_resize", resize_term(0, 0));
+    self.get_terminal_size()
+  }
+
+  /// Tells curses that the terminal was resized, for programs that detect
+  /// resizing themselves (such as with their own `SIGWINCH` handler).
+  ///
+  /// This updates the curses data structures for the new size. On Unix, if
+  /// the size actually changed, curses also queues a `TerminalResized` event
+  /// so that [`poll_events`](Curses::poll_events) reports it.
+  ///
+  /// * This is **not** safe to call from inside a signal handler. Have your
+  ///   handler set a flag (such as an `AtomicBool`), and then call this from
+  ///   your main loop when you see the flag set.
+  /// * On Windows this just syncs the size, pdcurses already reports resizes
+  ///   through `poll_events` on its own.
+  pub fn notify_resize(&mut self) -> Result<(), &'static str> {
+    #[cfg(unix)]
+    {
+      unsafe_call_result!("notify_resize", resizeterm(0, 0))
+    }
+    #[cfg(windows)]
+    {
+      unsafe_call_result!("notify_resize", resize_term(0, 0))
+    }
   }
 
   /// Assigns the timeout to use with [`poll_events`](Curses::poll_events).
@@ -316,7 +1982,98 @@ impl Curses {
   ///
   /// The default is to have blocking input.
   pub fn set_timeout(&mut self, time: i32) {
-    unsafe_void!(wtimeout(self.ptr, time))
+    unsafe_void!(wtimeout(self.ptr, time));
+    CURSES_TIMEOUT.store(time, Ordering::SeqCst);
+  }
+
+  /// Assigns the timeout to use with [`poll_events`](Curses::poll_events).
+  ///
+  /// * `None`: infinite time, `poll_events` is blocking.
+  /// * `Some(d)`: wait up to `d` before returning `None`. A zero duration means
+  ///   that `poll_events` returns `None` immediately if no input is ready.
+  ///   Durations are rounded down to whole milliseconds, and durations longer
+  ///   than `i32::MAX` milliseconds are cut down to that.
+  pub fn set_timeout_duration(&mut self, timeout: Option<Duration>) {
+    let ms = match timeout {
+      None => -1,
+      Some(d) => d.as_millis().try_into().unwrap_or(i32::MAX),
+    };
+    self.set_timeout(ms)
+  }
+
+  /// Gets the timeout used with [`poll_events`](Curses::poll_events).
+  ///
+  /// See [`set_timeout`](Curses::set_timeout) for what the value means.
+  pub fn get_timeout(&self) -> i32 {
+    CURSES_TIMEOUT.load(Ordering::SeqCst)
+  }
+
+  /// Sets how many milliseconds to wait after an ESC byte to see if it's the
+  /// start of an escape sequence (such as an arrow key).
+  ///
+  /// * The ncurses default is 1000ms, which makes a bare ESC press feel laggy.
+  /// * Lowering this risks escape sequences that arrive slowly (such as over a
+  ///   network connection) being read as an ESC followed by other keys.
+  /// * On Windows this does nothing, because pdcurses doesn't get keys as
+  ///   escape sequences in the first place.
+  pub fn set_escape_delay(&mut self, ms: i32) -> Result<(), &'static str> {
+    #[cfg(unix)]
+    {
+      unsafe_call_result!("set_escape_delay", set_escdelay(ms))
+    }
+    #[cfg(windows)]
+    {
+      let _ = ms;
+      Ok(())
+    }
+  }
+
+  /// Teaches curses that the given escape sequence means the given key.
+  ///
+  /// This is for terminals that send unusual sequences for some keys. After
+  /// this, when the terminal sends `seq` it's reported as `key`.
+  ///
+  /// * Errors if `seq` contains a null byte.
+  /// * On Windows this does nothing, because pdcurses doesn't get keys as
+  ///   escape sequences in the first place.
+  pub fn define_key(
+    &mut self, seq: &str, key: CursesKey,
+  ) -> Result<(), &'static str> {
+    #[cfg(unix)]
+    {
+      let seq = CString::new(seq).map_err(|_| "define_key")?;
+      let code = CursesKey::to_curses_code(Some(key));
+      unsafe_call_result!("define_key", define_key(seq.as_ptr(), code as _))
+    }
+    #[cfg(windows)]
+    {
+      let _ = (seq, key);
+      Ok(())
+    }
+  }
+
+  /// Enables or disables curses recognizing the escape sequence for a special
+  /// key.
+  ///
+  /// While disabled, the key's sequence comes through as individual ascii
+  /// bytes instead of as the key.
+  ///
+  /// * Errors if the key isn't a special key that curses knows a sequence for
+  ///   (such as any `Ascii` key).
+  /// * On Windows this always errors, pdcurses doesn't support it.
+  pub fn set_key_enabled(
+    &mut self, key: CursesKey, on: bool,
+  ) -> Result<(), &'static str> {
+    #[cfg(unix)]
+    {
+      let code = CursesKey::to_curses_code(Some(key));
+      unsafe_call_result!("set_key_enabled", keyok(code as _, on))
+    }
+    #[cfg(windows)]
+    {
+      let _ = (key, on);
+      Err("set_key_enabled")
+    }
   }
 
   /// Gets an input event.
@@ -326,91 +2083,331 @@ impl Curses {
   /// * If the terminal is resized, that shows up as a type of "key".
   /// * If you have a timeout set and the time expires, you get `None` back.
   pub fn poll_events(&mut self) -> Option<CursesKey> {
-    const ERR_U32: u32 = ERR as u32;
-    const KEY_F64: u32 = KEY_F0 + 64;
-    match (unsafe { wgetch(self.ptr) }) as u32 {
-      ERR_U32 => None,
-      ascii if (ascii <= u8::MAX as u32) => Some(CursesKey::Ascii(ascii as u8)),
-      #[cfg(windows)]
-      KEY_A1 => Some(CursesKey::Home),
-      #[cfg(windows)]
-      KEY_A2 => Some(CursesKey::ArrowUp),
-      #[cfg(windows)]
-      KEY_A3 => Some(CursesKey::PageUp),
-      #[cfg(windows)]
-      KEY_B1 => Some(CursesKey::ArrowLeft),
-      #[cfg(windows)]
-      KEY_B3 => Some(CursesKey::ArrowRight),
-      #[cfg(windows)]
-      KEY_C1 => Some(CursesKey::End),
-      #[cfg(windows)]
-      KEY_C2 => Some(CursesKey::ArrowDown),
-      #[cfg(windows)]
-      KEY_C3 => Some(CursesKey::PageDown),
-      #[cfg(windows)]
-      PADENTER => Some(CursesKey::Enter),
-      #[cfg(windows)]
-      PADSLASH => Some(CursesKey::Ascii(b'/')),
-      #[cfg(windows)]
-      PADSTAR => Some(CursesKey::Ascii(b'*')),
-      #[cfg(windows)]
-      PADMINUS => Some(CursesKey::Ascii(b'-')),
-      #[cfg(windows)]
-      PADPLUS => Some(CursesKey::Ascii(b'+')),
-      //
-      KEY_BACKSPACE => Some(CursesKey::Backspace),
-      KEY_UP => Some(CursesKey::ArrowUp),
-      KEY_DOWN => Some(CursesKey::ArrowDown),
-      KEY_LEFT => Some(CursesKey::ArrowLeft),
-      KEY_RIGHT => Some(CursesKey::ArrowRight),
-      KEY_IC => Some(CursesKey::Insert),
-      KEY_DC => Some(CursesKey::Delete),
-      KEY_HOME => Some(CursesKey::Home),
-      KEY_END => Some(CursesKey::End),
-      KEY_PPAGE => Some(CursesKey::PageUp),
-      KEY_NPAGE => Some(CursesKey::PageDown),
-      KEY_B2 => Some(CursesKey::Keypad5NoNumlock),
-      KEY_RESIZE => {
+    let key = CursesKey::from_curses_code(unsafe { wgetch(self.ptr) } as u32);
+    self.finish_key(key)
+  }
+
+  /// Gets an input event along with the raw code that curses gave for it.
+  ///
+  /// This is for debugging. If you get an `UnknownKey`, the raw code is what
+  /// to include when you file an issue about it.
+  ///
+  /// * `None` if the timeout expired.
+  /// * The key is otherwise the same as [`poll_events`](Curses::poll_events)
+  ///   would have given.
+  pub fn poll_events_raw(&mut self) -> Option<(Option<CursesKey>, i32)> {
+    let raw = unsafe { wgetch(self.ptr) };
+    if raw == ERR {
+      return None;
+    }
+    let key = self.finish_key(CursesKey::from_curses_code(raw as u32));
+    Some((key, raw))
+  }
+
+  /// Gets an input event, reading whole (wide) characters.
+  ///
+  /// This works like [`poll_events`](Curses::poll_events), except that any
+  /// non-ascii text input is returned as a single `Char` event instead of one
+  /// event per byte.
+  ///
+  /// * Ascii input is still returned as `Ascii`, so that the same key always
+  ///   gives the same event no matter which polling method you use.
+  /// * On Unix, ncurses decodes input according to the process's locale, and a
+  ///   Rust program starts in the "C" locale. You need to call `setlocale`
+  ///   (eg: via the `libc` crate) with a UTF-8 locale before init for
+  ///   non-ascii input to decode properly.
+  pub fn poll_wide_events(&mut self) -> Option<CursesKey> {
+    let mut wch: wint_t = 0;
+    let ret = unsafe { wget_wch(self.ptr, &mut wch) };
+    self.finish_key(CursesKey::from_wide_code(ret, wch as u32))
+  }
+
+  /// Does the extra work for a key that we just polled.
+  fn finish_key(&mut self, key: Option<CursesKey>) -> Option<CursesKey> {
+    match key {
+      Some(CursesKey::TerminalResized) => {
         unsafe { resize_term(0, 0) };
-        Some(CursesKey::TerminalResized)
+        key
+      }
+      Some(CursesKey::Ascii(8)) | Some(CursesKey::Ascii(127))
+        if self.normalize_backspace =>
+      {
+        Some(CursesKey::Backspace)
+      }
+      _ => key,
+    }
+  }
+
+  /// Sets if [`poll_events`](Curses::poll_events) should turn `Ascii(8)` (BS)
+  /// and `Ascii(127)` (DEL) into `Backspace`.
+  ///
+  /// Depending on the terminal, the backspace key might send any of
+  /// `KEY_BACKSPACE`, BS, or DEL. This is separate from the delete key, which
+  /// comes in as `Delete`. If you're making a line editor you probably want
+  /// this on, so that backspace works the same everywhere. If you need to tell
+  /// Ctrl+H apart from backspace you'll want it off.
+  ///
+  /// * Off by default.
+  pub fn set_normalize_backspace(&mut self, on: bool) {
+    self.normalize_backspace = on;
+  }
+
+  /// Gets an input event using the timeout given just for this call.
+  ///
+  /// * `None`: block until there's an event.
+  /// * `Some(ms)`: works like [`set_timeout`](Curses::set_timeout) with that
+  ///   value.
+  ///
+  /// The previous timeout is restored afterwards, so other calls to
+  /// [`poll_events`](Curses::poll_events) aren't affected.
+  pub fn poll_events_timeout(&mut self, ms: Option<i32>) -> Option<CursesKey> {
+    let old_timeout = self.get_timeout();
+    self.set_timeout(ms.unwrap_or(-1));
+    let key = self.poll_events();
+    self.set_timeout(old_timeout);
+    key
+  }
+
+  /// Makes an endless iterator of input events, for a "tick or input" loop.
+  ///
+  /// The timeout is set once with
+  /// [`set_timeout_duration`](Curses::set_timeout_duration), and then each
+  /// `next` call is a [`poll_events`](Curses::poll_events) call. So each item
+  /// is either `Some(key)`, or `None` when the time ran out with no input.
+  ///
+  /// * The iterator never ends on its own, you'll need to `break` out of the
+  ///   loop.
+  /// * The timeout stays set afterwards.
+  pub fn events_with_timeout(
+    &mut self, d: Duration,
+  ) -> impl Iterator<Item = Option<CursesKey>> + '_ {
+    self.set_timeout_duration(Some(d));
+    core::iter::from_fn(move || Some(self.poll_events()))
+  }
+
+  /// Checks the next input event without taking it out of the event queue.
+  ///
+  /// This polls with a zero timeout and, if there was an event, immediately
+  /// pushes it back with [`un_get_event`](Curses::un_get_event).
+  ///
+  /// * Returns `None` right away if no input is ready.
+  /// * Events pushed back always go to the front of the queue, so if you
+  ///   already pushed back other events then this can change their order.
+  pub fn peek_event(&mut self) -> Option<CursesKey> {
+    let old_timeout = self.get_timeout();
+    self.set_timeout(0);
+    let key = self.poll_events();
+    self.set_timeout(old_timeout);
+    if key.is_some() {
+      let _ = self.un_get_event(key);
+    }
+    key
+  }
+
+  /// Gets an input event, collapsing a run of resize events into one.
+  ///
+  /// Dragging a terminal's border can send a storm of resize events. When
+  /// this gets a `TerminalResized`, it keeps reading with a zero timeout for
+  /// as long as more resize events are already waiting, and then returns a
+  /// single `TerminalResized`. The first non-resize event found (if any) is
+  /// pushed back so the next poll returns it.
+  ///
+  /// Otherwise this works like [`poll_events`](Curses::poll_events).
+  pub fn poll_events_debounced(&mut self) -> Option<CursesKey> {
+    let key = self.poll_events();
+    if key == Some(CursesKey::TerminalResized) {
+      let old_timeout = self.get_timeout();
+      self.set_timeout(0);
+      loop {
+        match self.poll_events() {
+          Some(CursesKey::TerminalResized) => continue,
+          None => break,
+          other => {
+            let _ = self.un_get_event(other);
+            break;
+          }
+        }
+      }
+      self.set_timeout(old_timeout);
+    }
+    key
+  }
+
+  /// Blocks until one of the listed keys is pressed, ignoring all other input.
+  ///
+  /// * `TerminalResized` is always returned right away, even if it isn't
+  ///   listed, so that you can re-layout the screen.
+  /// * The timeout is set to blocking while this waits, and then the previous
+  ///   timeout is restored.
+  pub fn wait_for_keys(&mut self, keys: &[CursesKey]) -> CursesKey {
+    let old_timeout = self.get_timeout();
+    self.set_timeout(-1);
+    let key = loop {
+      match self.poll_events() {
+        Some(CursesKey::TerminalResized) => break CursesKey::TerminalResized,
+        Some(key) if keys.contains(&key) => break key,
+        _ => continue,
       }
-      KEY_ENTER => Some(CursesKey::Enter),
-      //
-      f if (f >= KEY_F0 && f <= KEY_F64) => {
-        Some(CursesKey::Function((f - KEY_F0) as u8))
+    };
+    self.set_timeout(old_timeout);
+    key
+  }
+
+  /// Blocks until a key other than `TerminalResized` is pressed.
+  ///
+  /// Every time the terminal is resized while waiting, `on_resize` is called
+  /// with the new terminal size so that you can re-layout the screen.
+  ///
+  /// * The timeout is set to blocking while this waits, and then the previous
+  ///   timeout is restored.
+  pub fn poll_with_resize<F: FnMut(&mut Curses, TerminalSize)>(
+    &mut self, mut on_resize: F,
+  ) -> CursesKey {
+    let old_timeout = self.get_timeout();
+    self.set_timeout(-1);
+    let key = loop {
+      match self.poll_events() {
+        Some(CursesKey::TerminalResized) => {
+          let size = self.get_terminal_size();
+          on_resize(self, size);
+        }
+        Some(key) => break key,
+        None => continue,
       }
-      other => Some(CursesKey::UnknownKey(other)),
+    };
+    self.set_timeout(old_timeout);
+    key
+  }
+
+  /// Gets an input event, along with the modifier keys held for it.
+  ///
+  /// This otherwise works like [`poll_events`](Curses::poll_events).
+  ///
+  /// * Windows: all modifiers are reported by pdcurses.
+  /// * Unix: only `alt` is reported, and only on a best-effort basis. Most
+  ///   terminals send Alt+key as an ESC byte followed by the key, so if an ESC
+  ///   is immediately followed by another key that key is returned with `alt`
+  ///   set. This is only a guess: a real Escape press followed quickly by
+  ///   another key looks the same. If the ESC is followed by another
+  ///   `Escape` or by `TerminalResized`, those aren't key chords, so the ESC
+  ///   is returned as a plain `Escape` and the next event is left for the
+  ///   next poll. Control chords just arrive as the matching ascii control
+  ///   code.
+  pub fn poll_events_with_modifiers(
+    &mut self,
+  ) -> Option<(CursesKey, KeyModifiers)> {
+    let key = self.poll_events()?;
+    #[cfg(windows)]
+    {
+      let bits = unsafe { PDC_get_key_modifiers() };
+      let modifiers = KeyModifiers {
+        shift: bits & PDC_KEY_MODIFIER_SHIFT != 0,
+        ctrl: bits & PDC_KEY_MODIFIER_CONTROL != 0,
+        alt: bits & PDC_KEY_MODIFIER_ALT != 0,
+      };
+      Some((key, modifiers))
+    }
+    #[cfg(unix)]
+    {
+      if key == CursesKey::Escape {
+        let old_delay = self.get_timeout();
+        self.set_timeout(0);
+        let next = self.poll_events();
+        self.set_timeout(old_delay);
+        match next {
+          Some(CursesKey::Escape) | Some(CursesKey::TerminalResized) => {
+            // Not a chord, so this event gets its own poll. If it can't be
+            // pushed back there's nothing better to do than drop it.
+            let _ = self.un_get_event(next);
+          }
+          Some(next) => {
+            let alt = KeyModifiers { alt: true, ..KeyModifiers::default() };
+            return Some((next, alt));
+          }
+          None => (),
+        }
+      }
+      Some((key, KeyModifiers::default()))
     }
   }
 
   /// Pushes this event to the front of the event queue so that the next
   /// `poll_events` returns this value.
+  ///
+  /// A `Char` event is pushed back as a wide character, so it should be read
+  /// with [`poll_wide_events`](Curses::poll_wide_events).
   pub fn un_get_event(
     &mut self, event: Option<CursesKey>,
   ) -> Result<(), &'static str> {
-    let ev: u32 = match event {
-      None => ERR as u32,
-      Some(CursesKey::Ascii(ascii)) => ascii as u32,
-      Some(CursesKey::Function(f)) => KEY_F0 + (f as u32),
-      Some(CursesKey::Enter) => KEY_ENTER,
-      Some(CursesKey::Backspace) => KEY_BACKSPACE,
-      Some(CursesKey::ArrowUp) => KEY_UP,
-      Some(CursesKey::ArrowDown) => KEY_DOWN,
-      Some(CursesKey::ArrowLeft) => KEY_LEFT,
-      Some(CursesKey::ArrowRight) => KEY_RIGHT,
-      Some(CursesKey::Insert) => KEY_IC,
-      Some(CursesKey::Delete) => KEY_DC,
-      Some(CursesKey::Home) => KEY_HOME,
-      Some(CursesKey::End) => KEY_END,
-      Some(CursesKey::PageUp) => KEY_PPAGE,
-      Some(CursesKey::PageDown) => KEY_NPAGE,
-      Some(CursesKey::Keypad5NoNumlock) => KEY_B2,
-      Some(CursesKey::TerminalResized) => KEY_RESIZE,
-      Some(CursesKey::UnknownKey(u)) => u,
-    };
+    if let Some(CursesKey::Char(ch)) = event {
+      let wch: wchar_t = (ch as u32).try_into().map_err(|_| "un_get_event")?;
+      return unsafe_call_result!("un_get_event", unget_wch(wch));
+    }
+    let ev = CursesKey::to_curses_code(event);
     unsafe_call_result!("un_get_event", ungetch(ev as i32))
   }
 
+  /// Pushes several events to the front of the event queue, so that the next
+  /// polls return them in the order given. Returns how many were pushed.
+  ///
+  /// Curses only has room for a limited number of pushed back events. The
+  /// events are pushed starting from the end of the slice, so if the queue
+  /// fills up then it's the events at the *start* of the slice that are left
+  /// out. If the count returned is less than `events.len()`, the queue was
+  /// full.
+  pub fn un_get_events(
+    &mut self, events: &[Option<CursesKey>],
+  ) -> Result<usize, &'static str> {
+    let mut count = 0;
+    for event in events.iter().rev() {
+      if self.un_get_event(*event).is_err() {
+        break;
+      }
+      count += 1;
+    }
+    if count == 0 && !events.is_empty() {
+      Err("un_get_events")
+    } else {
+      Ok(count)
+    }
+  }
+
+  /// Gets the byte the user's terminal settings use for "erase the previous
+  /// character" (often DEL or BS).
+  pub fn erase_char(&self) -> u8 {
+    unsafe { erasechar() as u8 }
+  }
+
+  /// Gets the byte the user's terminal settings use for "erase the whole
+  /// line" (often Ctrl+U).
+  pub fn kill_char(&self) -> u8 {
+    unsafe { killchar() as u8 }
+  }
+
+  /// Gets a human readable name for a key, such as `"^C"` or `"KEY_F(3)"`.
+  ///
+  /// * Printable ascii and `Char` keys are just the character itself.
+  /// * Other names come from curses, so they vary a little between ncurses and
+  ///   pdcurses.
+  /// * If curses doesn't have a name for the key, you get the `Debug` output
+  ///   of the key instead.
+  pub fn key_name(&self, key: CursesKey) -> String {
+    match key {
+      CursesKey::Ascii(ascii) if ascii.is_ascii_graphic() || ascii == b' ' => {
+        return char::from(ascii).to_string()
+      }
+      CursesKey::Char(ch) => return ch.to_string(),
+      _ => (),
+    }
+    let code = CursesKey::to_curses_code(Some(key));
+    let ptr = unsafe { keyname(code as _) };
+    if ptr.is_null() {
+      format!("{:?}", key)
+    } else {
+      unsafe { std::ffi::CStr::from_ptr(ptr) }.to_string_lossy().into_owned()
+    }
+  }
+
   /// Flushes all pending key events.
   pub fn flush_events(&mut self) -> Result<(), &'static str> {
     unsafe_call_result!("flush_events", flushinp())
@@ -421,13 +2418,72 @@ impl Curses {
     // ensure curses mode, in case a panic left us in shell mode
     let _ = self.refresh();
     unsafe_always_ok!(def_prog_mode());
-    unsafe_call_result!("shell_mode", endwin())
-      .map(move |_| CursesShell { win: self })
+    unsafe_call_result!("shell_mode", endwin())?;
+    // `endwin` should already have done this, but the shell's tty settings
+    // (line editing, etc) are what makes shell mode useful, so be sure.
+    let _ = unsafe_call_result!("shell_mode", reset_shell_mode());
+    // Anything that was buffered before now should show up before whatever
+    // the user prints during shell mode.
+    let _ = std::io::stdout().flush();
+    Ok(CursesShell { win: self })
+  }
+
+  /// Runs the closure with the terminal in shell mode, then returns to curses
+  /// mode.
+  ///
+  /// While `f` runs, `stdin`, `stdout`, and `stderr` all work normally, so
+  /// this is the easy way to pop out and run an external command. This is
+  /// the same as holding a [`shell_mode`](Curses::shell_mode) guard for the
+  /// duration of `f`.
+  pub fn with_shell<R>(
+    &mut self, f: impl FnOnce() -> R,
+  ) -> Result<R, &'static str> {
+    let _sh = self.shell_mode().map_err(|_| "with_shell")?;
+    Ok(f())
+  }
+
+  /// If curses mode is currently suspended (`endwin` has been called).
+  ///
+  /// This is true while a [`CursesShell`] is alive, or if a panic ended curses
+  /// mode. Calling [`refresh`](Curses::refresh) resumes curses mode.
+  pub fn is_suspended(&self) -> bool {
+    unsafe { isendwin() }
   }
 
   /// If the terminal supports colors at all.
+  ///
+  /// When this is `false`, the methods that set colors all return the error
+  /// `"no color support"` without calling into curses.
   pub fn has_color(&self) -> bool {
-    unsafe { has_colors() }
+    self.color_available
+  }
+
+  /// Errors if color can't be used.
+  fn require_color(&self) -> Result<(), &'static str> {
+    if self.color_available {
+      Ok(())
+    } else {
+      Err("no color support")
+    }
+  }
+
+  /// Gathers up what the terminal can do, such as for including in a bug
+  /// report.
+  pub fn capabilities(&self) -> Capabilities {
+    let name = unsafe { termname() };
+    let term_name = if name.is_null() {
+      String::new()
+    } else {
+      unsafe { std::ffi::CStr::from_ptr(name) }.to_string_lossy().into_owned()
+    };
+    Capabilities {
+      has_color: self.has_color(),
+      can_change_colors: self.can_change_colors(),
+      color_count: unsafe { COLORS }.max(0) as u32,
+      color_pair_count: unsafe { COLOR_PAIRS }.max(0) as u32,
+      attributes: self.supported_attributes(),
+      term_name,
+    }
   }
 
   /// If the terminal is able to change the RGB values of a given [`ColorID`]
@@ -451,6 +2507,18 @@ impl Curses {
       .map(ColorPair)
   }
 
+  /// Iterates over every color id this terminal supports.
+  pub fn color_ids(&self) -> impl Iterator<Item = ColorID> {
+    let end = self.get_max_color_id_inclusive().map(|c| c.0 as u16 + 1);
+    (0..end.unwrap_or(0)).map(|n| ColorID(n as u8))
+  }
+
+  /// Iterates over every color pair this terminal supports.
+  pub fn color_pairs(&self) -> impl Iterator<Item = ColorPair> {
+    let max = self.get_max_color_pair_inclusive().map(ColorPair::get);
+    (1..=max.unwrap_or(0)).filter_map(ColorPair::new)
+  }
+
   /// Sets the color id to use the RGB values given, or closest approximation
   /// available.
   ///
@@ -458,6 +2526,7 @@ impl Curses {
   pub fn set_color_id_rgb(
     &mut self, c: ColorID, [r, g, b]: [f32; 3],
   ) -> Result<(), &'static str> {
+    self.require_color()?;
     let r_i16 = (r.max(0.0).min(1.0) * 1000.0) as i16;
     let g_i16 = (g.max(0.0).min(1.0) * 1000.0) as i16;
     let b_i16 = (b.max(0.0).min(1.0) * 1000.0) as i16;
@@ -467,6 +2536,38 @@ impl Curses {
     )
   }
 
+  /// Sets the color id to use the RGB values given, or closest approximation
+  /// available.
+  ///
+  /// Each byte is scaled from `0 ..= 255` into the curses range of
+  /// `0 ..= 1000`.
+  pub fn set_color_id_rgb8(
+    &mut self, c: ColorID, [r, g, b]: [u8; 3],
+  ) -> Result<(), &'static str> {
+    self.require_color()?;
+    let r_i16 = (r as i32 * 1000 / 255) as i16;
+    let g_i16 = (g as i32 * 1000 / 255) as i16;
+    let b_i16 = (b as i32 * 1000 / 255) as i16;
+    unsafe_call_result!(
+      "set_color_id_rgb8",
+      init_color(c.0.into(), r_i16, g_i16, b_i16)
+    )
+  }
+
+  /// Sets the color id to use the `0xRRGGBB` value given, or closest
+  /// approximation available.
+  ///
+  /// The top byte of `hex` is ignored.
+  pub fn set_color_id_hex(
+    &mut self, c: ColorID, hex: u32,
+  ) -> Result<(), &'static str> {
+    self.require_color()?;
+    let r = (hex >> 16) as u8;
+    let g = (hex >> 8) as u8;
+    let b = hex as u8;
+    self.set_color_id_rgb8(c, [r, g, b]).map_err(|_| "set_color_id_hex")
+  }
+
   /// Gets the RGB values of the given color id.
   pub fn get_color_id_rgb(&self, c: ColorID) -> Result<[f32; 3], &'static str> {
     let mut r_i16 = 0;
@@ -493,21 +2594,80 @@ impl Curses {
   pub fn set_color_pair_content(
     &mut self, pair: ColorPair, fg: ColorID, bg: ColorID,
   ) -> Result<(), &'static str> {
+    self.require_color()?;
     unsafe_call_result!(
       "set_color_pair_content",
       init_pair(pair.0.get().into(), fg.0.into(), bg.0.into())
     )
   }
 
-  /// Gets the RGB values of the given color id.
+  /// Lets color pairs use the terminal's own default foreground and
+  /// background colors.
+  ///
+  /// Without this, curses picks white on black for anything that doesn't
+  /// say otherwise. After calling this, color pair 0 (no color pair) keeps the
+  /// user's terminal colors (including a transparent background), and you can
+  /// use [`set_color_pair_content_opt`](Curses::set_color_pair_content_opt) to
+  /// make other color pairs that use the defaults.
+  pub fn use_default_colors(&mut self) -> Result<(), &'static str> {
+    self.require_color()?;
+    unsafe_call_result!("use_default_colors", use_default_colors())
+  }
+
+  /// Sets the foreground and background used by color pair 0 (no color
+  /// pair).
+  ///
+  /// Color pair 0 can't be changed with
+  /// [`set_color_pair_content`](Curses::set_color_pair_content), so this is
+  /// how you give your whole program a base color scheme.
+  ///
+  /// * This is an ncurses extension. pdcurses emulates it on a best-effort
+  ///   basis, which should generally work but isn't guaranteed to match
+  ///   ncurses exactly.
+  /// * Like other color functions, this only works after color has been
+  ///   started.
+  pub fn assume_default_colors(
+    &mut self, fg: ColorID, bg: ColorID,
+  ) -> Result<(), &'static str> {
+    self.require_color()?;
+    unsafe_call_result!(
+      "assume_default_colors",
+      assume_default_colors(fg.0.into(), bg.0.into())
+    )
+  }
+
+  /// Assigns the selected color pair to use the foreground and background
+  /// specified, where `None` means the terminal's default color.
+  ///
+  /// Using `None` only works after
+  /// [`use_default_colors`](Curses::use_default_colors) has been called.
+  ///
+  /// Note: The default color can't be a [`ColorID`] value because every `u8`
+  /// is a real palette slot on a 256 color terminal.
+  pub fn set_color_pair_content_opt(
+    &mut self, pair: ColorPair, fg: Option<ColorID>, bg: Option<ColorID>,
+  ) -> Result<(), &'static str> {
+    self.require_color()?;
+    let fg: i16 = fg.map(|c| c.0.into()).unwrap_or(-1);
+    let bg: i16 = bg.map(|c| c.0.into()).unwrap_or(-1);
+    unsafe_call_result!(
+      "set_color_pair_content_opt",
+      init_pair(pair.0.get().into(), fg, bg)
+    )
+  }
+
+  /// Gets the foreground and background color ids of the given color pair.
+  ///
+  /// Errors if either color is the terminal's default color (see
+  /// [`use_default_colors`](Curses::use_default_colors)).
   pub fn get_color_pair_content(
-    &self, c: ColorID,
+    &self, pair: ColorPair,
   ) -> Result<(ColorID, ColorID), &'static str> {
     let mut f_i16 = 0;
     let mut b_i16 = 0;
     unsafe_call_result!(
       "get_color_pair_content",
-      pair_content(c.0.into(), &mut f_i16, &mut b_i16)
+      pair_content(pair.0.get().into(), &mut f_i16, &mut b_i16)
     )
     .and_then(|_| match (u8::try_from(f_i16), u8::try_from(b_i16)) {
       (Ok(f), Ok(b)) => Ok((ColorID(f), ColorID(b))),
@@ -515,10 +2675,99 @@ impl Curses {
     })
   }
 
+  /// Checks if a color pair looks like it's been set up.
+  ///
+  /// Curses doesn't track this directly, so a pair counts as "not defined" if
+  /// reading its content fails, or if both colors are still the value they
+  /// start as (black on black, or the terminal's default colors on both
+  /// sides). That means a pair that was deliberately set to black on black
+  /// also reports `false`.
+  ///
+  /// * Always `false` if the terminal doesn't have color.
+  pub fn is_color_pair_defined(&self, pair: ColorPair) -> bool {
+    if self.require_color().is_err() {
+      return false;
+    }
+    let mut f_i16 = 0;
+    let mut b_i16 = 0;
+    let ret =
+      unsafe { pair_content(pair.0.get().into(), &mut f_i16, &mut b_i16) };
+    ret != ERR && !matches!((f_i16, b_i16), (0, 0) | (-1, -1))
+  }
+
+  /// Clears out all color pairs, such as when switching between color themes.
+  ///
+  /// This also forgets the color pairs handed out by
+  /// [`use_colors`](Curses::use_colors).
+  ///
+  /// * ncurses: the color pairs go back to being undefined.
+  /// * pdcurses: there's no direct way to do this, so every color pair is set
+  ///   to white on black instead.
+  ///
+  /// Either way, you'll need to set up each color pair again before using it.
+  pub fn reset_color_pairs(&mut self) -> Result<(), &'static str> {
+    self.require_color()?;
+    self.auto_color_pairs.clear();
+    #[cfg(unix)]
+    {
+      unsafe_void!(reset_color_pairs());
+      Ok(())
+    }
+    #[cfg(windows)]
+    {
+      let (fg, bg) = (COLOR_WHITE as i16, COLOR_BLACK as i16);
+      for pair in self.color_pairs() {
+        unsafe_call_result!(
+          "reset_color_pairs",
+          init_pair(pair.get().into(), fg, bg)
+        )?;
+      }
+      Ok(())
+    }
+  }
+
+  /// Sets the foreground and background colors for all newly printed glyphs
+  /// in one step.
+  ///
+  /// This picks a color pair for you, sets its colors, and makes it the
+  /// active color pair. Using the same colors again reuses the same color
+  /// pair.
+  ///
+  /// * Color pairs are handed out starting from the highest color pair and
+  ///   counting down, so that they're unlikely to clash with color pairs you
+  ///   set up yourself. If you mix the two, don't change the colors of a
+  ///   color pair that this gave out.
+  /// * Errors with `"no free color pairs"` when every color pair has been
+  ///   used. [`reset_color_pairs`](Curses::reset_color_pairs) starts over.
+  pub fn use_colors(
+    &mut self, fg: ColorID, bg: ColorID,
+  ) -> Result<(), &'static str> {
+    self.require_color()?;
+    let max = self.get_max_color_pair_inclusive().map(ColorPair::get);
+    let max = max.unwrap_or(0) as usize;
+    let index = match self.auto_color_pairs.iter().position(|&c| c == (fg, bg))
+    {
+      Some(index) => index,
+      None => {
+        let index = self.auto_color_pairs.len();
+        if index >= max {
+          return Err("no free color pairs");
+        }
+        let pair = ColorPair::new((max - index) as u8).unwrap();
+        self.set_color_pair_content(pair, fg, bg).map_err(|_| "use_colors")?;
+        self.auto_color_pairs.push((fg, bg));
+        index
+      }
+    };
+    let pair = ColorPair::new((max - index) as u8);
+    self.set_active_color_pair(pair).map_err(|_| "use_colors")
+  }
+
   /// Sets the default coloring for all newly printed glyphs.
   pub fn set_active_color_pair(
     &mut self, opt_pair: Option<ColorPair>,
   ) -> Result<(), &'static str> {
+    self.require_color()?;
     let p = opt_pair.map(|cp| cp.0.get()).unwrap_or(0).into();
     unsafe_call_result!(
       "set_active_color_pair",
@@ -526,6 +2775,27 @@ impl Curses {
     )
   }
 
+  /// Sets if curses may use the terminal's hardware insert/delete line
+  /// features.
+  ///
+  /// This can make scrolling much faster over a slow connection, but it's only
+  /// a hint, and the terminal might not support it anyway.
+  ///
+  /// * Off by default.
+  pub fn set_hw_insdel_line(&mut self, on: bool) -> Result<(), &'static str> {
+    unsafe_call_result!("set_hw_insdel_line", idlok(self.ptr, on))
+  }
+
+  /// Sets if curses may use the terminal's hardware insert/delete character
+  /// features.
+  ///
+  /// This is only a hint, and the terminal might not support it anyway.
+  ///
+  /// * On by default.
+  pub fn set_hw_insdel_char(&mut self, on: bool) {
+    unsafe_void!(idcok(self.ptr, on))
+  }
+
   /// Set if the window can be scrolled or not.
   ///
   /// * Off by default.
@@ -536,13 +2806,24 @@ impl Curses {
   /// Sets the top line and bottom line that mark the edges of the scrollable
   /// region.
   ///
-  /// Lines `0..=top` and `bottom..` will stay static when the window is
-  /// scrolled. All other lines will move 1 row upward.
+  /// Lines `top..=bottom` are the scroll region. When the window is scrolled
+  /// those lines move 1 row upward, and all lines outside the region stay
+  /// static.
   ///
   /// * By default the scroll region is the entire terminal.
+  /// * The region only has an effect if scrolling is enabled with
+  ///   [`set_scrollable`](Curses::set_scrollable).
+  /// * Errors with `"top greater than bottom"` if `top > bottom`, or with
+  ///   `"scroll region out of bounds"` if `bottom` isn't on the screen.
   pub fn set_scroll_region(
     &mut self, top: u32, bottom: u32,
   ) -> Result<(), &'static str> {
+    if top > bottom {
+      return Err("top greater than bottom");
+    }
+    if bottom >= self.get_physical_terminal_size().y_count {
+      return Err("scroll region out of bounds");
+    }
     unsafe_call_result!(
       "set_scroll_region",
       wsetscrreg(self.ptr, top as i32, bottom as i32)
@@ -558,6 +2839,46 @@ impl Curses {
     unsafe_call_result!("scroll", wscrl(self.ptr, n))
   }
 
+  /// Scrolls only the lines `top..=bottom` by the given number of lines.
+  ///
+  /// This turns on scrolling, sets the scroll region, scrolls, and then puts
+  /// the old scroll region and scrolling setting back. The `n` value works
+  /// like with [`scroll`](Curses::scroll).
+  ///
+  /// * Errors the same as [`set_scroll_region`](Curses::set_scroll_region) if
+  ///   the region isn't valid, in which case nothing is changed.
+  /// * On Windows the old settings can't be read back, so afterwards the
+  ///   scroll region is the entire terminal and scrolling is off (the
+  ///   defaults).
+  pub fn scroll_region(
+    &mut self, top: u32, bottom: u32, n: i32,
+  ) -> Result<(), &'static str> {
+    #[cfg(unix)]
+    let (was_scrollable, old_top, old_bottom) = {
+      let mut old_top = 0;
+      let mut old_bottom = 0;
+      unsafe_call_result!(
+        "scroll_region",
+        wgetscrreg(self.ptr, &mut old_top, &mut old_bottom)
+      )?;
+      (unsafe { is_scrollok(self.ptr) }, old_top, old_bottom)
+    };
+    #[cfg(windows)]
+    let (was_scrollable, old_top, old_bottom) =
+      (false, 0, self.get_physical_terminal_size().y_count as i32 - 1);
+    self.set_scroll_region(top, bottom)?;
+    let out = self
+      .set_scrollable(true)
+      .and_then(|_| self.scroll(n))
+      .map_err(|_| "scroll_region");
+    let restored = unsafe_call_result!(
+      "scroll_region",
+      wsetscrreg(self.ptr, old_top, old_bottom)
+    )
+    .and(self.set_scrollable(was_scrollable));
+    out.and(restored).map_err(|_| "scroll_region")
+  }
+
   /// Sets the cursor visibility.
   ///
   /// Returns the old visibility, or Err if it can't be set.
@@ -573,13 +2894,120 @@ impl Curses {
     })
   }
 
+  /// Sets the shape of the cursor, if the terminal supports it.
+  ///
+  /// This is separate from the [visibility](Curses::set_cursor_visibility),
+  /// and is commonly used by editors to show a different cursor in insert
+  /// mode.
+  ///
+  /// * This uses the terminal's `Ss` terminfo capability. If the terminal
+  ///   doesn't have that, this does nothing.
+  /// * The escape sequence is sent right away, to the same output as the rest
+  ///   of the screen (including the output file of a
+  ///   [`new_term`](Curses::new_term) screen).
+  /// * Curses doesn't reset this when it ends, so you might want to set
+  ///   `Default` before your program exits.
+  /// * On Windows this does nothing.
+  pub fn set_cursor_style(
+    &mut self, style: CursorStyle,
+  ) -> Result<(), &'static str> {
+    #[cfg(unix)]
+    {
+      use std::{
+        os::raw::{c_int, c_long},
+        sync::atomic::AtomicPtr,
+      };
+      // `putp` always writes to the process's stdout, which is only right for
+      // the real terminal. A `new_term` screen gets it in its own output file.
+      static NEW_TERM_OUT: AtomicPtr<FILE> =
+        AtomicPtr::new(core::ptr::null_mut());
+      extern "C" fn put_new_term(c: c_int) -> c_int {
+        unsafe { fputc(c, NEW_TERM_OUT.load(Ordering::SeqCst)) }
+      }
+      let putc: unsafe extern "C" fn(c_int) -> c_int =
+        match &self.new_term_screen {
+          Some(nt) => {
+            NEW_TERM_OUT.store(nt.out, Ordering::SeqCst);
+            put_new_term
+          }
+          None => putchar,
+        };
+      // Send anything curses has waiting first, so that the output stays in
+      // order. This has to happen before `tparm`, since curses reuses the
+      // buffer that `tparm` returns.
+      unsafe_call_result!("set_cursor_style", doupdate())?;
+      let ss = match self.terminfo_string("Ss") {
+        Some(ss) => ss,
+        None => return Ok(()),
+      };
+      let s = unsafe { tparm(ss.as_ptr(), style as c_long) };
+      if s.is_null() {
+        return Err("set_cursor_style");
+      }
+      unsafe_call_result!("set_cursor_style", tputs(s, 1, putc))?;
+      if unsafe { fflush(core::ptr::null_mut()) } != 0 {
+        return Err("set_cursor_style");
+      }
+      Ok(())
+    }
+    #[cfg(windows)]
+    {
+      let _ = style;
+      Ok(())
+    }
+  }
+
+  /// Gets the cursor visibility.
+  ///
+  /// Curses can only report the visibility by changing it, so this sets the
+  /// visibility and then immediately sets it back.
+  ///
+  /// * Errors if the terminal doesn't support changing the visibility.
+  pub fn get_cursor_visibility(
+    &mut self,
+  ) -> Result<CursorVisibility, &'static str> {
+    let old = self
+      .set_cursor_visibility(CursorVisibility::Normal)
+      .map_err(|_| "get_cursor_visibility")?;
+    self.set_cursor_visibility(old).map_err(|_| "get_cursor_visibility")?;
+    Ok(old)
+  }
+
   /// Sets the background glyph.
+  ///
+  /// Every cell already on the screen is immediately changed to use the new
+  /// background. Use
+  /// [`set_background_future`](Curses::set_background_future) if you don't
+  /// want that.
   pub fn set_background<C: Into<CursesGlyph>>(
     &mut self, c: C,
   ) -> Result<(), &'static str> {
     unsafe_call_result!("set_background", wbkgd(self.ptr, c.into().as_chtype()))
   }
 
+  /// Sets the background glyph without changing any existing cells.
+  ///
+  /// Unlike with [`set_background`](Curses::set_background), only cells that
+  /// are drawn or cleared after this call will use the new background.
+  pub fn set_background_future<C: Into<CursesGlyph>>(&mut self, c: C) {
+    unsafe_void!(wbkgdset(self.ptr, c.into().as_chtype()))
+  }
+
+  /// Changes only the color pair of the background, keeping its character and
+  /// attributes.
+  ///
+  /// Like with [`set_background`](Curses::set_background), every cell already
+  /// on the screen is immediately changed, so this is an easy way to tint the
+  /// whole screen.
+  pub fn set_background_color(
+    &mut self, pair: ColorPair,
+  ) -> Result<(), &'static str> {
+    self.require_color()?;
+    let glyph =
+      CursesGlyph { opt_color_pair: Some(pair), ..self.get_background() };
+    self.set_background(glyph).map_err(|_| "set_background_color")
+  }
+
   /// Gets the background glyph.
   pub fn get_background(&self) -> CursesGlyph {
     CursesGlyph::from(unsafe { getbkgd(self.ptr) })
@@ -589,7 +3017,8 @@ impl Curses {
 /// A position on the screen.
 ///
 /// The upper left corner is considered to be the (0,0) position.
-#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct Position {
   /// The `x` position (aka `col`)
@@ -597,9 +3026,31 @@ pub struct Position {
   /// The `y` position (aka `row`)
   pub y: u32,
 }
+impl Position {
+  /// Makes a position from the `x` (column) and `y` (row) values.
+  #[inline]
+  pub const fn new(x: u32, y: u32) -> Self {
+    Self { x, y }
+  }
+}
+impl From<(u32, u32)> for Position {
+  /// The tuple is `(x, y)`.
+  #[inline]
+  fn from((x, y): (u32, u32)) -> Self {
+    Self { x, y }
+  }
+}
+impl From<Position> for (u32, u32) {
+  /// The tuple is `(x, y)`.
+  #[inline]
+  fn from(p: Position) -> Self {
+    (p.x, p.y)
+  }
+}
 
 /// Used to return info about the upper bounds of the screen.
-#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct TerminalSize {
   /// The number of `x` positions on the screen, valid `x` will be `0..x_count`
@@ -607,9 +3058,107 @@ pub struct TerminalSize {
   /// The number of `y` positions on the screen, valid `y` will be `0..y_count`
   pub y_count: u32,
 }
+impl TerminalSize {
+  /// Makes a size from the number of columns and the number of rows.
+  #[inline]
+  pub const fn new(x_count: u32, y_count: u32) -> Self {
+    Self { x_count, y_count }
+  }
+}
+impl From<(u32, u32)> for TerminalSize {
+  /// The tuple is `(x_count, y_count)`.
+  #[inline]
+  fn from((x_count, y_count): (u32, u32)) -> Self {
+    Self { x_count, y_count }
+  }
+}
+impl From<TerminalSize> for (u32, u32) {
+  /// The tuple is `(x_count, y_count)`.
+  #[inline]
+  fn from(s: TerminalSize) -> Self {
+    (s.x_count, s.y_count)
+  }
+}
+
+/// A rectangular area of the screen.
+///
+/// The area covers `x` in `origin.x..origin.x+size.x_count`, and likewise for
+/// `y`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Rect {
+  /// The upper left corner.
+  pub origin: Position,
+  /// The number of cells across and down.
+  pub size: TerminalSize,
+}
+impl Rect {
+  /// Makes a rect from the upper left corner and the size.
+  #[inline]
+  pub const fn new(origin: Position, size: TerminalSize) -> Self {
+    Self { origin, size }
+  }
+
+  /// If the rect has no cells in it.
+  #[inline]
+  pub const fn is_empty(self) -> bool {
+    self.size.x_count == 0 || self.size.y_count == 0
+  }
+
+  /// One past the last `x` value within the rect.
+  #[inline]
+  pub fn x_end(self) -> u32 {
+    self.origin.x.saturating_add(self.size.x_count)
+  }
+
+  /// One past the last `y` value within the rect.
+  #[inline]
+  pub fn y_end(self) -> u32 {
+    self.origin.y.saturating_add(self.size.y_count)
+  }
+
+  /// If the position is within the rect.
+  #[inline]
+  pub fn contains(self, p: Position) -> bool {
+    p.x >= self.origin.x
+      && p.x < self.x_end()
+      && p.y >= self.origin.y
+      && p.y < self.y_end()
+  }
+
+  /// The area that's within both rects, if any.
+  pub fn intersection(self, other: Rect) -> Option<Rect> {
+    let x = self.origin.x.max(other.origin.x);
+    let y = self.origin.y.max(other.origin.y);
+    let x_end = self.x_end().min(other.x_end());
+    let y_end = self.y_end().min(other.y_end());
+    if x < x_end && y < y_end {
+      Some(Rect {
+        origin: Position { x, y },
+        size: TerminalSize { x_count: x_end - x, y_count: y_end - y },
+      })
+    } else {
+      None
+    }
+  }
+
+  /// Cuts the rect down to only the part that's on a screen of the given
+  /// size.
+  ///
+  /// If no part of the rect is on the screen, the output is empty.
+  pub fn clamp_to(self, screen: TerminalSize) -> Rect {
+    let whole_screen = Rect { origin: Position { x: 0, y: 0 }, size: screen };
+    self.intersection(whole_screen).unwrap_or(Rect {
+      origin: self.origin,
+      size: TerminalSize { x_count: 0, y_count: 0 },
+    })
+  }
+}
 
 /// A single ascii value to draw to the screen, along with color and attributes.
-#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(C, align(4))]
 // Note(Lokathor): align is 4 so that &CursesGlyph is also a valid &chtype
 pub struct CursesGlyph {
@@ -622,15 +3171,37 @@ pub struct CursesGlyph {
   /// The other attributes to use.
   pub attributes: Attributes,
 }
-impl From<u8> for CursesGlyph {
-  fn from(ascii: u8) -> Self {
-    Self { ascii, opt_color_pair: None, attributes: Attributes(0) }
+impl From<u8> for CursesGlyph {
+  fn from(ascii: u8) -> Self {
+    Self { ascii, opt_color_pair: None, attributes: Attributes(0) }
+  }
+}
+impl From<char> for CursesGlyph {
+  fn from(ch: char) -> Self {
+    let ascii = ch as u8;
+    Self { ascii, opt_color_pair: None, attributes: Attributes(0) }
+  }
+}
+impl From<(u8, ColorPair)> for CursesGlyph {
+  fn from((ascii, pair): (u8, ColorPair)) -> Self {
+    Self { ascii, opt_color_pair: Some(pair), attributes: Attributes(0) }
+  }
+}
+impl From<(char, ColorPair)> for CursesGlyph {
+  fn from((ch, pair): (char, ColorPair)) -> Self {
+    let ascii = ch as u8;
+    Self { ascii, opt_color_pair: Some(pair), attributes: Attributes(0) }
+  }
+}
+impl From<(u8, ColorPair, Attributes)> for CursesGlyph {
+  fn from((ascii, pair, attributes): (u8, ColorPair, Attributes)) -> Self {
+    Self { ascii, opt_color_pair: Some(pair), attributes }
   }
 }
-impl From<char> for CursesGlyph {
-  fn from(ch: char) -> Self {
+impl From<(char, ColorPair, Attributes)> for CursesGlyph {
+  fn from((ch, pair, attributes): (char, ColorPair, Attributes)) -> Self {
     let ascii = ch as u8;
-    Self { ascii, opt_color_pair: None, attributes: Attributes(0) }
+    Self { ascii, opt_color_pair: Some(pair), attributes }
   }
 }
 impl From<chtype> for CursesGlyph {
@@ -638,7 +3209,24 @@ impl From<chtype> for CursesGlyph {
     unsafe { core::mem::transmute(cht) }
   }
 }
+impl Default for CursesGlyph {
+  /// A space with no color pair and no attributes.
+  #[inline]
+  fn default() -> Self {
+    Self::SPACE
+  }
+}
 impl CursesGlyph {
+  /// A space with no color pair and no attributes.
+  pub const SPACE: CursesGlyph = CursesGlyph {
+    ascii: b' ',
+    opt_color_pair: None,
+    attributes: Attributes(0),
+  };
+
+  /// An empty cell, the same as [`SPACE`](CursesGlyph::SPACE).
+  pub const BLANK: CursesGlyph = CursesGlyph::SPACE;
+
   /// Turn into a `chtype` for sending to ncurses.
   fn as_chtype(self) -> chtype {
     unsafe { core::mem::transmute(self) }
@@ -646,6 +3234,7 @@ impl CursesGlyph {
 }
 
 /// Use with [`set_cursor_visibility`](Curses::set_cursor_visibility)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum CursorVisibility {
   /// Cursor is invisible.
@@ -656,6 +3245,141 @@ pub enum CursorVisibility {
   VeryVisible = 2,
 }
 
+/// A summary of what the terminal can do.
+///
+/// Made with [`capabilities`](Curses::capabilities).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+  /// If color can be used (see [`has_color`](Curses::has_color)).
+  pub has_color: bool,
+  /// If color ids can be changed (see
+  /// [`can_change_colors`](Curses::can_change_colors)).
+  pub can_change_colors: bool,
+  /// How many color ids there are.
+  ///
+  /// This can be more than 256, but `yacurses` can only use the first 256.
+  pub color_count: u32,
+  /// How many color pairs there are, including pair 0.
+  ///
+  /// This can be more than 256, but `yacurses` can only use the first 256.
+  pub color_pair_count: u32,
+  /// The supported attributes (see
+  /// [`supported_attributes`](Curses::supported_attributes)).
+  pub attributes: Attributes,
+  /// The terminal's name (eg: `"xterm-256color"`).
+  pub term_name: String,
+}
+
+/// The render state saved by [`save_state`](Curses::save_state).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CursesState {
+  /// The cursor position.
+  pub position: Position,
+  /// The attributes for newly printed glyphs.
+  pub attributes: Attributes,
+  /// The color pair for newly printed glyphs.
+  pub opt_color_pair: Option<ColorPair>,
+  /// The background glyph.
+  pub background: CursesGlyph,
+}
+
+/// How the soft label keys are laid out along the bottom of the screen.
+///
+/// Use with [`init_with_soft_labels`](Curses::init_with_soft_labels)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum SoftLabelLayout {
+  /// 8 labels, in groups of 3, 2, and 3.
+  ThreeTwoThree = 0,
+  /// 8 labels, in groups of 4 and 4.
+  FourFour = 1,
+  /// 12 labels, in groups of 4, 4, and 4.
+  FourFourFour = 2,
+  /// 12 labels, in groups of 4, 4, and 4, with a line above showing the
+  /// number of each label. This takes up two lines of the screen.
+  FourFourFourIndexed = 3,
+}
+
+/// What kind of alert [`alert`](Curses::alert) gives.
+///
+/// Use with [`set_alert_preference`](Curses::set_alert_preference)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertPreference {
+  /// Flash the screen if possible, otherwise beep.
+  Auto,
+  /// Always beep.
+  Audible,
+  /// Always flash the screen.
+  Visual,
+}
+impl Default for AlertPreference {
+  #[inline]
+  fn default() -> Self {
+    AlertPreference::Auto
+  }
+}
+
+/// How text is placed within a wider field.
+///
+/// Use with [`print_padded`](Curses::print_padded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Align {
+  /// Text starts at the left edge of the field.
+  Left,
+  /// Text is centered in the field, leaning left if it can't be exact.
+  Center,
+  /// Text ends at the right edge of the field.
+  Right,
+}
+
+/// The ways that [`try_init`](Curses::try_init) can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursesInitError {
+  /// Another `Curses` is still alive.
+  AlreadyActive,
+  /// The `TERM` environment variable isn't set.
+  NoTerm,
+  /// Curses couldn't start with the terminal type given by `TERM` (usually
+  /// because there's no terminfo entry for it).
+  UnknownTerminal,
+  /// Curses started, but `cbreak` mode couldn't be set (usually because
+  /// `stdin` isn't a tty).
+  NoCbreak,
+}
+impl fmt::Display for CursesInitError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      CursesInitError::AlreadyActive => "Curses is already active.",
+      CursesInitError::NoTerm => "The TERM variable isn't set.",
+      CursesInitError::UnknownTerminal => "Couldn't start the terminal.",
+      CursesInitError::NoCbreak => "Couldn't set `cbreak` mode.",
+    })
+  }
+}
+impl std::error::Error for CursesInitError {}
+
+/// The shape of the cursor.
+///
+/// Use with [`set_cursor_style`](Curses::set_cursor_style)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum CursorStyle {
+  /// Whatever the terminal's own setting is.
+  Default = 0,
+  /// A blinking block.
+  BlinkingBlock = 1,
+  /// A steady block.
+  SteadyBlock = 2,
+  /// A blinking underline.
+  BlinkingUnderline = 3,
+  /// A steady underline.
+  SteadyUnderline = 4,
+  /// A blinking vertical bar.
+  BlinkingBar = 5,
+  /// A steady vertical bar.
+  SteadyBar = 6,
+}
+
 /// Names a color within curses.
 ///
 /// This is **not** an actual RGB color value. It's just an index into a color
@@ -668,7 +3392,8 @@ pub enum CursorVisibility {
 /// This type has some associated constants.
 /// Each constant names the id value that is most likely to display as that
 /// color by default.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct ColorID(pub u8);
 #[allow(missing_docs)]
@@ -682,6 +3407,30 @@ impl ColorID {
   pub const CYAN: ColorID = ColorID(COLOR_CYAN as u8);
   pub const WHITE: ColorID = ColorID(COLOR_WHITE as u8);
 }
+impl ColorID {
+  /// The RGB that xterm shows for this color id by default, for the 8
+  /// standard colors.
+  ///
+  /// This is just a fixed table, it doesn't look at the live palette (use
+  /// [`get_color_id_rgb`](Curses::get_color_id_rgb) for that), so it works
+  /// even before curses is initialized. Other terminals use similar but not
+  /// identical colors.
+  ///
+  /// Other color ids give `None`.
+  pub const fn default_rgb(self) -> Option<[u8; 3]> {
+    match self {
+      ColorID::BLACK => Some([0, 0, 0]),
+      ColorID::RED => Some([205, 0, 0]),
+      ColorID::GREEN => Some([0, 205, 0]),
+      ColorID::YELLOW => Some([205, 205, 0]),
+      ColorID::BLUE => Some([0, 0, 238]),
+      ColorID::MAGENTA => Some([205, 0, 205]),
+      ColorID::CYAN => Some([0, 205, 205]),
+      ColorID::WHITE => Some([229, 229, 229]),
+      _ => None,
+    }
+  }
+}
 
 /// Names a foreground / background color pairing within curses.
 ///
@@ -699,16 +3448,47 @@ impl ColorID {
 /// If you change the colors of a color pair, all character cells on the screen
 /// using that pairing will have their displayed colors immediately changed
 /// accordingly.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct ColorPair(pub NonZeroU8);
+impl ColorPair {
+  /// Makes a color pair from a byte, or `None` if the byte is 0.
+  #[inline]
+  pub const fn new(n: u8) -> Option<Self> {
+    match NonZeroU8::new(n) {
+      Some(nz) => Some(ColorPair(nz)),
+      None => None,
+    }
+  }
+
+  /// Gets the color pair as a byte.
+  #[inline]
+  pub const fn get(self) -> u8 {
+    self.0.get()
+  }
+}
+impl TryFrom<u8> for ColorPair {
+  type Error = &'static str;
+  #[inline]
+  fn try_from(n: u8) -> Result<Self, Self::Error> {
+    ColorPair::new(n).ok_or("ColorPair::try_from")
+  }
+}
+impl From<ColorPair> for u8 {
+  #[inline]
+  fn from(cp: ColorPair) -> Self {
+    cp.get()
+  }
+}
 
 /// Attributes that can be applied to a character's cell (a bitflag value).
 ///
 /// Useful attributes have named constants.
 /// Other bits are generally ineffective.
 /// None of the bits can cause a safety concern.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Attributes(pub u16);
 impl Attributes {
@@ -814,6 +3594,65 @@ impl Attributes {
   pub const TOP: Attributes = Attributes(1 << 13);
   pub const VERTICAL: Attributes = Attributes(1 << 14);
   */
+
+  /// All of the named attributes.
+  const NAMED: [Attributes; 9] = [
+    Attributes::STANDOUT,
+    Attributes::UNDERLINE,
+    Attributes::REVERSE,
+    Attributes::BLINK,
+    Attributes::DIM,
+    Attributes::BOLD,
+    Attributes::ALT_CHAR_SET,
+    Attributes::INVIS,
+    Attributes::ITALIC,
+  ];
+
+  /// Iterates over the named attributes that are set in this value.
+  ///
+  /// * Each output has exactly one bit set.
+  /// * Bits that don't have a named constant are skipped.
+  /// * Attributes that aren't a single bit on this platform are also skipped
+  ///   (eg: `STANDOUT` is `REVERSE | BOLD` on Windows, so those two are
+  ///   given instead).
+  pub fn iter(self) -> impl Iterator<Item = Attributes> {
+    Attributes::NAMED
+      .iter()
+      .copied()
+      .filter(move |a| a.0.is_power_of_two() && (self.0 & a.0) != 0)
+  }
+
+  /// Builds a full curses attribute word (an `attr_t`), the same as
+  /// `attr | COLOR_PAIR(n)` would in C.
+  ///
+  /// The `Attributes` bits are the upper half of the attribute word, but the
+  /// color pair doesn't always fit in there:
+  /// * ncurses keeps the color pair in bits 8 through 15, *below* the
+  ///   attributes.
+  /// * pdcurses keeps the color pair in bits 24 through 31, which overlaps the
+  ///   upper byte of `Attributes`.
+  ///
+  /// So the color pair is passed separately and put in the right spot here.
+  /// This is for use with raw curses functions such as `wattr_set`.
+  pub const fn to_attr_word(self, opt_pair: Option<ColorPair>) -> u32 {
+    let pair = match opt_pair {
+      Some(p) => p.get() as u32,
+      None => 0,
+    };
+    let pair_shift = if cfg!(unix) { 8 } else { 24 };
+    ((self.0 as u32) << 16) | (pair << pair_shift)
+  }
+
+  /// Splits a full curses attribute word into the attributes and the color
+  /// pair.
+  ///
+  /// This is the reverse of [`to_attr_word`](Attributes::to_attr_word).
+  pub const fn from_attr_word(word: u32) -> (Attributes, Option<ColorPair>) {
+    let (mask, pair_shift) =
+      if cfg!(unix) { (u16::MAX, 8) } else { (0x00FF, 24) };
+    let attrs = Attributes((word >> 16) as u16 & mask);
+    (attrs, ColorPair::new((word >> pair_shift) as u8))
+  }
 }
 impl BitAnd for Attributes {
   type Output = Self;
@@ -856,7 +3695,18 @@ impl BitXorAssign for Attributes {
 }
 
 /// The types of input keys that `ncurses` can generate.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// The numpad gives the same keys on all platforms:
+///
+/// * The digits (with numlock on) and the `+`, `-`, `*`, `/`, and `.` keys
+///   give `Ascii` of that symbol, even when the terminal sends them as special
+///   keypad keys.
+/// * The numpad Enter gives `Enter` if the terminal sends it as a special key
+///   (otherwise it's the same as the main Enter key).
+/// * With numlock off, 7, 9, 1, and 3 give `Home`, `PageUp`, `End`, and
+///   `PageDown`, the 8, 4, 6, and 2 keys give the arrow keys, and 5 gives
+///   `Keypad5NoNumlock`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CursesKey {
   /// An ascii input (most all the keys with symbols on them).
   Ascii(u8),
@@ -869,6 +3719,14 @@ pub enum CursesKey {
   Enter,
   /// Backspace key
   Backspace,
+  /// Escape key (byte 27).
+  ///
+  /// On Unix, special keys arrive as escape sequences that start with this
+  /// same byte, and curses tells them apart by waiting for the
+  /// [escape delay](Curses::set_escape_delay). With a low escape delay, a
+  /// sequence that arrives slowly (such as an arrow key over ssh) can show up
+  /// as `Escape` followed by other keys.
+  Escape,
   /// Arrow upward (arrow key or numpad without numlock)
   ArrowUp,
   /// Arrow downward (arrow key or numpad without numlock)
@@ -896,6 +3754,11 @@ pub enum CursesKey {
   /// These aren't the best supported because the terminal emulator often eat
   /// them before the program sees it.
   Function(u8),
+  /// A non-ascii text character.
+  ///
+  /// This is only returned by
+  /// [`poll_wide_events`](Curses::poll_wide_events).
+  Char(char),
   /// Some unknown input value.
   ///
   /// You might want to file an issue to get this value included.
@@ -906,6 +3769,156 @@ impl CursesKey {
   pub const fn from_ascii(ascii: u8) -> Self {
     CursesKey::Ascii(ascii)
   }
+
+  /// Decodes a `wgetch` return value.
+  ///
+  /// Every key this produces will turn back into a code that decodes to that
+  /// same key via [`to_curses_code`](CursesKey::to_curses_code).
+  fn from_curses_code(code: u32) -> Option<Self> {
+    const ERR_U32: u32 = ERR as u32;
+    const KEY_F64: u32 = KEY_F0 + 64;
+    match code {
+      ERR_U32 => None,
+      27 => Some(CursesKey::Escape),
+      ascii if (ascii <= u8::MAX as u32) => Some(CursesKey::Ascii(ascii as u8)),
+      KEY_A1 => Some(CursesKey::Home),
+      KEY_A3 => Some(CursesKey::PageUp),
+      KEY_C1 => Some(CursesKey::End),
+      KEY_C3 => Some(CursesKey::PageDown),
+      #[cfg(windows)]
+      KEY_A2 => Some(CursesKey::ArrowUp),
+      #[cfg(windows)]
+      KEY_B1 => Some(CursesKey::ArrowLeft),
+      #[cfg(windows)]
+      KEY_B3 => Some(CursesKey::ArrowRight),
+      #[cfg(windows)]
+      KEY_C2 => Some(CursesKey::ArrowDown),
+      // xterm and friends send "begin" for the middle of the numpad.
+      #[cfg(unix)]
+      KEY_BEG => Some(CursesKey::Keypad5NoNumlock),
+      #[cfg(windows)]
+      PADENTER => Some(CursesKey::Enter),
+      #[cfg(windows)]
+      PADSLASH => Some(CursesKey::Ascii(b'/')),
+      #[cfg(windows)]
+      PADSTAR => Some(CursesKey::Ascii(b'*')),
+      #[cfg(windows)]
+      PADMINUS => Some(CursesKey::Ascii(b'-')),
+      #[cfg(windows)]
+      PADPLUS => Some(CursesKey::Ascii(b'+')),
+      #[cfg(windows)]
+      PADSTOP => Some(CursesKey::Ascii(b'.')),
+      #[cfg(windows)]
+      PAD0 => Some(CursesKey::Ascii(b'0')),
+      // ncurses gives keys from extended terminfo capabilities (such as the
+      // numpad operators) codes above `KEY_MAX` that are picked at runtime, so
+      // we have to go by their names.
+      #[cfg(unix)]
+      extended if extended > KEY_MAX => {
+        Some(Self::from_extended_key_code(extended))
+      }
+      //
+      KEY_BACKSPACE => Some(CursesKey::Backspace),
+      KEY_UP => Some(CursesKey::ArrowUp),
+      KEY_DOWN => Some(CursesKey::ArrowDown),
+      KEY_LEFT => Some(CursesKey::ArrowLeft),
+      KEY_RIGHT => Some(CursesKey::ArrowRight),
+      KEY_IC => Some(CursesKey::Insert),
+      KEY_DC => Some(CursesKey::Delete),
+      KEY_HOME => Some(CursesKey::Home),
+      KEY_END => Some(CursesKey::End),
+      KEY_PPAGE => Some(CursesKey::PageUp),
+      KEY_NPAGE => Some(CursesKey::PageDown),
+      KEY_B2 => Some(CursesKey::Keypad5NoNumlock),
+      KEY_RESIZE => Some(CursesKey::TerminalResized),
+      KEY_ENTER => Some(CursesKey::Enter),
+      //
+      f if (f >= KEY_F0 && f <= KEY_F64) => {
+        Some(CursesKey::Function((f - KEY_F0) as u8))
+      }
+      other => Some(CursesKey::UnknownKey(other)),
+    }
+  }
+
+  /// Decodes an ncurses key code for an extended terminfo capability.
+  #[cfg(unix)]
+  fn from_extended_key_code(code: u32) -> Self {
+    let ptr = unsafe { keyname(code as _) };
+    if ptr.is_null() {
+      return CursesKey::UnknownKey(code);
+    }
+    match unsafe { std::ffi::CStr::from_ptr(ptr) }.to_bytes() {
+      b"kpADD" => CursesKey::Ascii(b'+'),
+      b"kpSUB" => CursesKey::Ascii(b'-'),
+      b"kpMUL" => CursesKey::Ascii(b'*'),
+      b"kpDIV" => CursesKey::Ascii(b'/'),
+      b"kpDOT" => CursesKey::Ascii(b'.'),
+      b"kpCMA" => CursesKey::Ascii(b','),
+      b"kpZRO" => CursesKey::Ascii(b'0'),
+      b"kp5" => CursesKey::Keypad5NoNumlock,
+      _ => CursesKey::UnknownKey(code),
+    }
+  }
+
+  /// Decodes a `wget_wch` return value along with the character it wrote.
+  fn from_wide_code(ret: i32, wch: u32) -> Option<Self> {
+    match ret {
+      ERR => None,
+      KEY_CODE_YES => Self::from_curses_code(wch),
+      _ => match wch {
+        27 => Some(CursesKey::Escape),
+        ascii if ascii < 128 => Some(CursesKey::Ascii(ascii as u8)),
+        other => Some(
+          core::char::from_u32(other)
+            .map(CursesKey::Char)
+            .unwrap_or(CursesKey::UnknownKey(other)),
+        ),
+      },
+    }
+  }
+
+  /// Encodes a key as a value for `ungetch`.
+  ///
+  /// Keys that [`from_curses_code`](CursesKey::from_curses_code) never
+  /// produces (such as `Function(65)`, or `UnknownKey` holding a known code)
+  /// won't necessarily decode back to themselves.
+  fn to_curses_code(key: Option<Self>) -> u32 {
+    match key {
+      None => ERR as u32,
+      Some(CursesKey::Ascii(ascii)) => ascii as u32,
+      Some(CursesKey::Function(f)) => KEY_F0 + (f as u32),
+      Some(CursesKey::Enter) => KEY_ENTER,
+      Some(CursesKey::Backspace) => KEY_BACKSPACE,
+      Some(CursesKey::Escape) => 27,
+      Some(CursesKey::ArrowUp) => KEY_UP,
+      Some(CursesKey::ArrowDown) => KEY_DOWN,
+      Some(CursesKey::ArrowLeft) => KEY_LEFT,
+      Some(CursesKey::ArrowRight) => KEY_RIGHT,
+      Some(CursesKey::Insert) => KEY_IC,
+      Some(CursesKey::Delete) => KEY_DC,
+      Some(CursesKey::Home) => KEY_HOME,
+      Some(CursesKey::End) => KEY_END,
+      Some(CursesKey::PageUp) => KEY_PPAGE,
+      Some(CursesKey::PageDown) => KEY_NPAGE,
+      Some(CursesKey::Keypad5NoNumlock) => KEY_B2,
+      Some(CursesKey::TerminalResized) => KEY_RESIZE,
+      Some(CursesKey::Char(ch)) => ch as u32,
+      Some(CursesKey::UnknownKey(u)) => u,
+    }
+  }
+}
+
+/// The modifier keys held during an input event.
+///
+/// Use with [`poll_events_with_modifiers`](Curses::poll_events_with_modifiers)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct KeyModifiers {
+  /// A shift key was held.
+  pub shift: bool,
+  /// A control key was held.
+  pub ctrl: bool,
+  /// An alt key was held.
+  pub alt: bool,
 }
 
 /// While you hold this, the terminal is in shell mode.
@@ -920,8 +3933,25 @@ impl CursesKey {
 pub struct CursesShell<'a> {
   win: &'a mut Curses,
 }
+impl<'a> CursesShell<'a> {
+  /// Flushes `stdout` and `stderr`.
+  ///
+  /// This happens automatically when the `CursesShell` drops, but you can call
+  /// it yourself if you want your output to show up sooner.
+  pub fn flush(&mut self) {
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+  }
+}
 impl<'a> Drop for CursesShell<'a> {
   fn drop(&mut self) {
+    // Output printed during shell mode must get out before curses redraws.
+    self.flush();
+    // Restore the tty settings saved by `def_prog_mode` before redrawing.
+    let _ = unsafe_call_result!("reset_prog_mode", reset_prog_mode());
+    // Whatever ran in shell mode probably drew over the screen, so curses
+    // can't trust what it thinks is on the display. Force a full repaint.
+    let _ = unsafe_call_result!("redrawwin", redrawwin(self.win.ptr));
     unsafe_call_result!("refresh", wrefresh(self.win.ptr)).unwrap();
   }
 }
@@ -933,6 +3963,112 @@ impl<'a> Deref for CursesShell<'a> {
   }
 }
 
+/// Collects glyphs to draw at the cursor, and draws them in batches.
+///
+/// Made with [`writer`](Curses::writer).
+///
+/// * Glyphs are drawn along the cursor's row, and anything past the right
+///   edge of the screen is cut off instead of wrapping.
+/// * When dropped, any pending glyphs are drawn and the screen is refreshed.
+///   Errors can't be reported from a drop, so call
+///   [`flush`](GlyphWriter::flush) if you need to know about them.
+pub struct GlyphWriter<'a> {
+  win: &'a mut Curses,
+  pending: Vec<CursesGlyph>,
+}
+impl<'a> GlyphWriter<'a> {
+  /// Adds a glyph to the current run.
+  #[inline]
+  pub fn push<C: Into<CursesGlyph>>(&mut self, c: C) {
+    self.pending.push(c.into());
+  }
+
+  /// Adds each byte of the str to the current run as a plain glyph.
+  ///
+  /// A `'\n'` draws the run so far and moves the cursor to the start of the
+  /// next line. Otherwise each byte is one cell, like with
+  /// [`print_str`](Curses::print_str).
+  ///
+  /// * Errors if there's no next line to move to.
+  pub fn push_str(&mut self, s: &str) -> Result<(), &'static str> {
+    let mut lines = s.split('\n');
+    if let Some(first) = lines.next() {
+      self.pending.extend(first.bytes().map(CursesGlyph::from));
+    }
+    for line in lines {
+      self.draw_pending().map_err(|_| "push_str")?;
+      let y = self.win.get_cursor_position().y + 1;
+      self.win.move_cursor(Position { x: 0, y }).map_err(|_| "push_str")?;
+      self.pending.extend(line.bytes().map(CursesGlyph::from));
+    }
+    Ok(())
+  }
+
+  /// Draws the current run, then moves the cursor so that a new run starts
+  /// at the position given.
+  pub fn move_cursor(&mut self, p: Position) -> Result<(), &'static str> {
+    self.draw_pending().map_err(|_| "move_cursor")?;
+    self.win.move_cursor(p)
+  }
+
+  /// Draws the current run and refreshes the screen.
+  pub fn flush(&mut self) -> Result<(), &'static str> {
+    self.draw_pending().map_err(|_| "flush")?;
+    self.win.refresh()
+  }
+
+  /// Draws the pending glyphs and moves the cursor past them (but not past
+  /// the last column).
+  fn draw_pending(&mut self) -> Result<(), &'static str> {
+    if self.pending.is_empty() {
+      return Ok(());
+    }
+    let count = self.pending.len().min(self.win.row_room());
+    let out = self.win.copy_glyphs(&self.pending[..count]);
+    self.pending.clear();
+    out?;
+    let p = self.win.get_cursor_position();
+    let last_col =
+      self.win.get_physical_terminal_size().x_count.saturating_sub(1);
+    let x = (p.x + count as u32).min(last_col);
+    unsafe_call_result!("", wmove(self.win.ptr, p.y as _, x as _))
+  }
+}
+impl<'a> Drop for GlyphWriter<'a> {
+  fn drop(&mut self) {
+    let _ = self.flush();
+  }
+}
+
+/// While you hold this, draw through it as normal, and when you drop it the
+/// screen is refreshed.
+///
+/// Made with [`auto_refresh`](Curses::auto_refresh).
+#[repr(transparent)]
+pub struct AutoRefresh<'a> {
+  win: &'a mut Curses,
+}
+impl<'a> Drop for AutoRefresh<'a> {
+  fn drop(&mut self) {
+    // Errors can't be reported from a drop, and the worst case is that the
+    // screen didn't update.
+    let _ = self.win.refresh();
+  }
+}
+impl<'a> Deref for AutoRefresh<'a> {
+  type Target = Curses;
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    self.win
+  }
+}
+impl<'a> DerefMut for AutoRefresh<'a> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.win
+  }
+}
+
 macro_rules! acs_getter {
   ($fn_name:ident, $ch:expr, $d:expr) => {
     #[doc = $d]
@@ -993,3 +4129,245 @@ impl Curses {
   acs_getter!(acs_urcorner, 'k', "Upper right corner of a box.");
   acs_getter!(acs_vline, 'x', "Vertical line");
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  #[cfg(unix)]
+  use std::path::PathBuf;
+
+  extern "C" {
+    fn scr_dump(filename: *const std::os::raw::c_char) -> std::os::raw::c_int;
+  }
+
+  /// Starts a headless `Curses` that writes to a temp file named after the
+  /// test.
+  ///
+  /// Only one `Curses` can be active at a time, so tests that use one wait
+  /// their turn.
+  #[cfg(unix)]
+  fn headless(name: &str) -> (Curses, PathBuf) {
+    let path = std::env::temp_dir().join(format!(
+      "yacurses-{}-{}",
+      std::process::id(),
+      name
+    ));
+    loop {
+      let out = File::create(&path).unwrap();
+      let input = File::open("/dev/null").unwrap();
+      match Curses::new_term("xterm-256color", out, input) {
+        Ok(win) => return (win, path),
+        Err(_) if CURSES_ACTIVE.load(Ordering::SeqCst) => {
+          std::thread::yield_now()
+        }
+        Err(e) => panic!("{}", e),
+      }
+    }
+  }
+
+  /// Everything that was written to a headless terminal's output file.
+  #[cfg(unix)]
+  fn take_output(path: &Path) -> String {
+    let bytes = std::fs::read(path).unwrap();
+    let _ = std::fs::remove_file(path);
+    String::from_utf8_lossy(&bytes).into_owned()
+  }
+
+  /// Every key that `from_curses_code` can produce, other than `UnknownKey`.
+  fn decodable_keys() -> Vec<CursesKey> {
+    let mut keys: Vec<CursesKey> = (0..=u8::MAX)
+      .filter(|&b| b != 27)
+      .map(CursesKey::Ascii)
+      .chain((0..=64).map(CursesKey::Function))
+      .collect();
+    keys.extend_from_slice(&[
+      CursesKey::TerminalResized,
+      CursesKey::Enter,
+      CursesKey::Backspace,
+      CursesKey::Escape,
+      CursesKey::ArrowUp,
+      CursesKey::ArrowDown,
+      CursesKey::ArrowLeft,
+      CursesKey::ArrowRight,
+      CursesKey::Insert,
+      CursesKey::Delete,
+      CursesKey::Home,
+      CursesKey::End,
+      CursesKey::PageUp,
+      CursesKey::PageDown,
+      CursesKey::Keypad5NoNumlock,
+    ]);
+    keys
+  }
+
+  #[test]
+  fn keys_round_trip() {
+    for key in decodable_keys() {
+      let code = CursesKey::to_curses_code(Some(key));
+      assert_eq!(CursesKey::from_curses_code(code), Some(key), "{:?}", key);
+    }
+    assert_eq!(CursesKey::to_curses_code(None), ERR as u32);
+    assert_eq!(CursesKey::from_curses_code(ERR as u32), None);
+  }
+
+  #[test]
+  fn function_key_range_ends() {
+    assert_eq!(
+      CursesKey::from_curses_code(KEY_F0),
+      Some(CursesKey::Function(0))
+    );
+    assert_eq!(
+      CursesKey::from_curses_code(KEY_F0 + 64),
+      Some(CursesKey::Function(64))
+    );
+    assert_eq!(
+      CursesKey::from_curses_code(KEY_F0 + 65),
+      Some(CursesKey::UnknownKey(KEY_F0 + 65))
+    );
+  }
+
+  #[test]
+  fn chars_round_trip() {
+    for &ch in &['é', '€', 'λ', '\u{7FF}'] {
+      let code = CursesKey::to_curses_code(Some(CursesKey::Char(ch)));
+      assert_eq!(CursesKey::from_wide_code(0, code), Some(CursesKey::Char(ch)));
+    }
+    assert_eq!(CursesKey::from_wide_code(0, 27), Some(CursesKey::Escape));
+    assert_eq!(
+      CursesKey::from_wide_code(0, b'a' as u32),
+      Some(CursesKey::Ascii(b'a'))
+    );
+    assert_eq!(CursesKey::from_wide_code(ERR, 0), None);
+  }
+
+  #[test]
+  fn backend_keypad_codes_round_trip() {
+    #[cfg(unix)]
+    let codes = [KEY_A1, KEY_A3, KEY_B2, KEY_C1, KEY_C3, KEY_BEG, KEY_ENTER];
+    #[cfg(windows)]
+    let codes = [
+      KEY_A1, KEY_A2, KEY_A3, KEY_B1, KEY_B2, KEY_B3, KEY_C1, KEY_C2, KEY_C3,
+      PADENTER, PADSLASH, PADSTAR, PADMINUS, PADPLUS, PADSTOP, PAD0,
+    ];
+    for &code in codes.iter() {
+      let key = CursesKey::from_curses_code(code);
+      assert!(!matches!(key, Some(CursesKey::UnknownKey(_))), "{:#x}", code);
+      let again = CursesKey::from_curses_code(CursesKey::to_curses_code(key));
+      assert_eq!(again, key, "{:#x}", code);
+    }
+  }
+
+  #[test]
+  fn unknown_codes_round_trip() {
+    #[cfg(unix)]
+    let codes = [KEY_CODE_YES as u32, 353, KEY_MAX, 0x1_0000];
+    #[cfg(windows)]
+    let codes = [KEY_CODE_YES as u32, 0x1ff, 0x300, 0x1_0000];
+    for &code in codes.iter() {
+      let key = CursesKey::from_curses_code(code);
+      assert_eq!(key, Some(CursesKey::UnknownKey(code)));
+      assert_eq!(CursesKey::to_curses_code(key), code);
+    }
+  }
+
+  #[test]
+  fn all_codes_decode_consistently() {
+    // Whatever a code decodes to must encode to a code with the same meaning.
+    for code in 0..0x300 {
+      let key = CursesKey::from_curses_code(code);
+      let again = CursesKey::from_curses_code(CursesKey::to_curses_code(key));
+      assert_eq!(again, key, "{:#x}", code);
+    }
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn screen_dump_is_drawn() {
+    let (mut win, path) = headless("dump-source");
+    win.print_str("SAVED TEXT").unwrap();
+    win.refresh().unwrap();
+    let dump = CString::new(format!("{}.dump", path.display())).unwrap();
+    unsafe_call_result!("scr_dump", scr_dump(dump.as_ptr())).unwrap();
+    drop(win);
+    take_output(&path);
+
+    let (mut win, path) = headless("dump-shown");
+    win.draw_screen_dump(&dump).unwrap();
+    let _ = std::fs::remove_file(dump.to_str().unwrap());
+    // Drawing more afterwards doesn't blank out the rest of the dump.
+    win.move_cursor(Position { x: 12, y: 0 }).unwrap();
+    win.print_str("MORE").unwrap();
+    win.refresh().unwrap();
+    assert_eq!(win.read_line(0, 16).unwrap(), "SAVED TEXT  MORE");
+    drop(win);
+    assert!(take_output(&path).contains("SAVED TEXT"));
+  }
+
+  /// Puts the cursor `cols` cells left of the bottom right corner, with
+  /// "TOP" on the first row so that scrolling would show.
+  #[cfg(unix)]
+  fn near_bottom_right(win: &mut Curses, cols: u32, scroll: bool) -> Position {
+    win.set_scrollable(scroll).unwrap();
+    win.print_str("TOP").unwrap();
+    let size = win.get_terminal_size();
+    let p = Position { x: size.x_count - cols, y: size.y_count - 1 };
+    win.move_cursor(p).unwrap();
+    p
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn truncated_print_doesnt_wrap_in_the_corner() {
+    let (mut win, path) = headless("truncated-corner");
+    let p = near_bottom_right(&mut win, 3, true);
+    win.print_str_truncated("abcdef", u32::MAX).unwrap();
+    assert_eq!(win.read_line(p.y, 256).unwrap().trim_start(), "abc");
+    assert_eq!(win.read_line(0, 3).unwrap(), "TOP");
+    assert_eq!(win.get_cursor_position(), Position { x: p.x + 2, ..p });
+    drop(win);
+    take_output(&path);
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn padded_field_doesnt_wrap_in_the_corner() {
+    let (mut win, path) = headless("padded-corner");
+    let p = near_bottom_right(&mut win, 5, false);
+    win.print_padded("ab", 5, Align::Right).unwrap();
+    assert_eq!(win.read_line(p.y, 256).unwrap().trim_start(), "ab");
+    assert_eq!(win.read_line(0, 3).unwrap(), "TOP");
+    assert_eq!(win.get_cursor_position(), Position { x: p.x + 4, ..p });
+    drop(win);
+    take_output(&path);
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn stamp_in_the_last_cell() {
+    let (mut win, path) = headless("stamp-corner");
+    let p = near_bottom_right(&mut win, 1, true);
+    win.move_cursor(Position { x: 1, y: 1 }).unwrap();
+    win.stamp(p, "XYZ", Attributes::BOLD, None).unwrap();
+    assert_eq!(win.read_line(p.y, 256).unwrap().trim_start(), "X");
+    assert_eq!(win.read_line(0, 3).unwrap(), "TOP");
+    assert_eq!(win.get_cursor_position(), Position { x: 1, y: 1 });
+    drop(win);
+    take_output(&path);
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn cursor_style_goes_to_the_screen_output() {
+    let (mut win, path) = headless("cursor-style");
+    win.print_str("BEFORE").unwrap();
+    win.refresh().unwrap();
+    win.set_cursor_style(CursorStyle::SteadyBlock).unwrap();
+    // It's sent right away, after what was already drawn.
+    let sent =
+      String::from_utf8_lossy(&std::fs::read(&path).unwrap()).into_owned();
+    let style = sent.find("\u{1b}[2 q").unwrap();
+    assert!(sent.find("BEFORE").unwrap() < style);
+    drop(win);
+    take_output(&path);
+  }
+}
@@ -2,10 +2,21 @@
 
 //! ncurses-specific declarations.
 
-use crate::curses_common::chtype;
+use crate::curses_common::{chtype, FILE, SCREEN, WINDOW};
+use std::os::raw::*;
 
+pub type wchar_t = c_int;
+pub type wint_t = c_uint;
+
+pub const KEY_A1: u32 = 348;
+pub const KEY_A3: u32 = 349;
 pub const KEY_B2: u32 = 350;
+pub const KEY_C1: u32 = 351;
+pub const KEY_C3: u32 = 352;
+pub const KEY_BEG: u32 = 354;
 pub const KEY_END: u32 = 360;
+pub const KEY_RESIZE: u32 = 410;
+pub const KEY_MAX: u32 = 511;
 
 // Note(Lokathor): READ ONLY!
 extern "C" {
@@ -14,3 +25,44 @@ extern "C" {
   /// This isn't filled in until *after* curses has been initialized.
   pub static mut acs_map: [chtype; 0usize];
 }
+
+extern "C" {
+  pub fn dup(fd: c_int) -> c_int;
+
+  pub fn fdopen(fd: c_int, mode: *const c_char) -> *mut FILE;
+
+  pub fn define_key(definition: *const c_char, keycode: c_int) -> c_int;
+
+  pub fn is_scrollok(win: *const WINDOW) -> bool;
+
+  pub fn keyok(keycode: c_int, enable: bool) -> c_int;
+
+  pub fn newterm(
+    term: *const c_char, outfd: *mut FILE, infd: *mut FILE,
+  ) -> *mut SCREEN;
+
+  pub fn reset_color_pairs();
+
+  pub fn resizeterm(lines: c_int, columns: c_int) -> c_int;
+
+  pub fn set_escdelay(ms: c_int) -> c_int;
+
+  pub fn tigetstr(capname: *const c_char) -> *mut c_char;
+
+  pub fn tparm(s: *const c_char, ...) -> *mut c_char;
+
+  pub fn tputs(
+    str: *const c_char, affcnt: c_int,
+    putc: unsafe extern "C" fn(c_int) -> c_int,
+  ) -> c_int;
+
+  pub fn ungetch(arg1: c_int) -> c_int;
+
+  pub fn unget_wch(arg1: wchar_t) -> c_int;
+
+  pub fn wgetscrreg(
+    win: *const WINDOW, top: *mut c_int, bottom: *mut c_int,
+  ) -> c_int;
+
+  pub fn wget_wch(arg1: *mut WINDOW, arg2: *mut wint_t) -> c_int;
+}
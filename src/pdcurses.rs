@@ -2,11 +2,19 @@
 
 //! pdcurses-specific declarations.
 
+use crate::curses_common::WINDOW;
+use std::os::raw::*;
+
+pub type wchar_t = c_ushort;
+pub type wint_t = c_ushort;
+
 pub const PADSLASH: u32 = 0x1ca;
 pub const PADENTER: u32 = 0x1cb;
+pub const PADSTOP: u32 = 0x1ce;
 pub const PADSTAR: u32 = 0x1cf;
 pub const PADMINUS: u32 = 0x1d0;
 pub const PADPLUS: u32 = 0x1d1;
+pub const PAD0: u32 = 0x1fa;
 pub const KEY_A1: u32 = 0x1c1;
 pub const KEY_A2: u32 = 0x1c2;
 pub const KEY_A3: u32 = 0x1c3;
@@ -17,3 +25,19 @@ pub const KEY_C1: u32 = 0x1c7;
 pub const KEY_C2: u32 = 0x1c8;
 pub const KEY_C3: u32 = 0x1c9;
 pub const KEY_END: u32 = 0x166;
+pub const KEY_RESIZE: u32 = 0x222;
+pub const PDC_KEY_MODIFIER_SHIFT: c_ulong = 1;
+pub const PDC_KEY_MODIFIER_CONTROL: c_ulong = 2;
+pub const PDC_KEY_MODIFIER_ALT: c_ulong = 4;
+
+extern "C" {
+  pub fn PDC_get_key_modifiers() -> c_ulong;
+
+  // `ungetch` is a macro for this in the pdcurses header.
+  #[link_name = "PDC_ungetch"]
+  pub fn ungetch(arg1: c_int) -> c_int;
+
+  pub fn unget_wch(arg1: wchar_t) -> c_int;
+
+  pub fn wget_wch(arg1: *mut WINDOW, arg2: *mut wint_t) -> c_int;
+}